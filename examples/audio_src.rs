@@ -2,10 +2,8 @@
  * SPDX-License-Identifier: MIT
  */
 use std::ffi::CString;
-use std::mem::size_of;
 use std::ops::Add;
 use std::rc::Rc;
-use std::slice;
 use std::sync::{Arc, Mutex};
 
 use pipewire_wrapper::core_api::core::Core;
@@ -107,33 +105,20 @@ fn format_param() -> pipewire_wrapper::Result<AllocPod<PodObjectRef>> {
 fn on_process(stream: &Rc<Stream>, accumulator: &Arc<Mutex<f64>>) {
     if let Some(buf) = stream.dequeue_buffer() {
         let spa_buf = buf.buffer_mut();
-        if let Some(data) = spa_buf.datas_mut().first_mut() {
-            if !data.data().is_null() {
-                let stride = CHANNELS * size_of::<AudioDataType>() as u32;
-                let n_frames = data.max_size() / stride; // Since 0.3.49 buf.requested can be used here
-                let data_slice = unsafe {
-                    slice::from_raw_parts_mut(
-                        data.data() as *mut AudioDataType,
-                        (n_frames * CHANNELS) as usize,
-                    )
-                };
+        if let Ok(mut audio) = spa_buf.interleaved_mut::<AudioDataType>(AudioFormat::F32, CHANNELS)
+        {
+            // Since 0.3.49 buf.requested can be used to limit the frames count
+            let n_frames = audio.frames();
 
-                let mut accumulator = accumulator.lock().unwrap();
-                for i in 0..n_frames {
-                    *accumulator = accumulator.add(PI_POW_2 * 440f64 / RATE) % PI_POW_2;
-                    let val = (accumulator.sin() * VOLUME * 16767f64) as AudioDataType;
-                    for c in 0..CHANNELS {
-                        data_slice[(i * CHANNELS + c) as usize] = val;
-                    }
-                }
-
-                let chunk = data.chunk_mut();
-                chunk.set_offset(0);
-                chunk.set_stride(stride as i32);
-                chunk.set_size(n_frames * stride);
-
-                stream.queue_buffer(buf).unwrap();
+            let mut accumulator = accumulator.lock().unwrap();
+            for frame in audio.iter_mut() {
+                *accumulator = accumulator.add(PI_POW_2 * 440f64 / RATE) % PI_POW_2;
+                let val = (accumulator.sin() * VOLUME * 16767f64) as AudioDataType;
+                frame.fill(val);
             }
+            audio.commit(n_frames).unwrap();
+
+            stream.queue_buffer(buf).unwrap();
         }
     }
 }
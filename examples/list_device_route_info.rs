@@ -3,26 +3,21 @@
  */
 extern crate pipewire_wrapper;
 
-use std::collections::HashMap;
 use std::ffi::CString;
 use std::rc::Rc;
 use std::sync::Mutex;
 
 use pipewire_wrapper::core_api::core::Core;
-use pipewire_wrapper::core_api::device::events::DeviceEventsBuilder;
 use pipewire_wrapper::core_api::device::{Device, DeviceRef};
-use pipewire_wrapper::core_api::loop_::Loop;
-use pipewire_wrapper::core_api::main_loop::MainLoop;
 use pipewire_wrapper::core_api::proxy::Proxied;
 use pipewire_wrapper::core_api::registry::events::RegistryEventsBuilder;
 use pipewire_wrapper::core_api::registry::Registry;
 use pipewire_wrapper::listeners::{ListenerId, OwnListeners};
-use pipewire_wrapper::spa::loop_::EventSource;
 use pipewire_wrapper::spa::param::ParamType;
 use pipewire_wrapper::spa::pod::object::param_route::ParamRouteType;
 use pipewire_wrapper::spa::pod::object::prop::{AudioChannel, ObjectPropType};
-use pipewire_wrapper::spa::pod::object::ObjectType;
-use pipewire_wrapper::spa::pod::{BasicType, PodRef, PodValue};
+use pipewire_wrapper::spa::pod::object::{ObjectType, PodObjectRef};
+use pipewire_wrapper::spa::pod::PodValue;
 
 #[derive(Debug, Default)]
 pub struct DeviceRouteInfo {
@@ -33,43 +28,30 @@ pub struct DeviceRouteInfo {
 }
 
 fn main() {
-    let core = Rc::new(Core::default());
-    let devices: Rc<Mutex<HashMap<u32, Device>>> = Rc::new(Mutex::default());
-    let device_added_queue = Rc::new(Mutex::new(Vec::<u32>::new()));
-    let main_loop = core.context().main_loop().clone();
+    let core = Core::default();
     let registry = core.get_registry(0).unwrap();
-    let device_added_event = add_device_added_event(
-        main_loop.clone(),
-        devices.clone(),
-        registry.clone(),
-        device_added_queue.clone(),
-    );
-    let _registry_listener =
-        add_registry_listener(registry, device_added_event, device_added_queue);
-    let quit_main_loop = {
-        let main_loop = main_loop.clone();
-        move |_| {
-            main_loop.quit().unwrap();
-        }
-    };
-    let _sigint_handler = main_loop.add_signal(signal_hook::consts::SIGINT, quit_main_loop.clone());
-    let _sigterm_handler = main_loop.add_signal(signal_hook::consts::SIGTERM, quit_main_loop);
+    let device_ids = Rc::new(Mutex::new(Vec::<u32>::new()));
+    let _registry_listener = add_registry_listener(&registry, device_ids.clone());
+
+    // Wait until all the existing globals are announced
+    core.roundtrip().unwrap();
 
-    println!("Running main loop");
-    main_loop.run().unwrap();
+    let device_ids = device_ids.lock().unwrap().clone();
+    for id in device_ids {
+        let device: Device = registry.bind_proxy(id, 0).unwrap();
+        let params = device.collect_params(ParamType::ROUTE, 0, 0, None).unwrap();
+        for (index, param) in params.iter().enumerate() {
+            print_route_info(index, param.as_pod());
+        }
+    }
 }
 
-fn add_registry_listener(
-    registry: Registry,
-    device_added_event: EventSource<'static, MainLoop>,
-    device_added_queue: Rc<Mutex<Vec<u32>>>,
-) -> ListenerId {
+fn add_registry_listener(registry: &Registry, device_ids: Rc<Mutex<Vec<u32>>>) -> ListenerId {
     let listener = RegistryEventsBuilder::default()
         .global(Box::new(
             move |id, _permissions, type_info, _version, _props| {
                 if type_info == DeviceRef::type_info() {
-                    device_added_queue.lock().unwrap().push(id);
-                    let _ = device_added_event.signal().unwrap();
+                    device_ids.lock().unwrap().push(id);
                 }
             },
         ))
@@ -77,79 +59,43 @@ fn add_registry_listener(
     registry.add_listener(listener)
 }
 
-fn add_device_added_event(
-    main_loop: MainLoop,
-    devices: Rc<Mutex<HashMap<u32, Device>>>,
-    registry: Registry,
-    device_added_queue: Rc<Mutex<Vec<u32>>>,
-) -> EventSource<'static, MainLoop> {
-    main_loop
-        .add_event(Box::new({
-            move |_count| {
-                let devices = &mut devices.lock().unwrap();
-                let device_added_queue = device_added_queue.lock().unwrap();
-                let new_device_ids: Vec<&u32> = device_added_queue
-                    .iter()
-                    .filter(|device_id| !devices.contains_key(device_id))
-                    .collect();
-                for &id in new_device_ids {
-                    let device: Device = registry.bind_proxy(id, 0).unwrap();
-                    let listener = DeviceEventsBuilder::default()
-                        .param(Box::new(device_param_callback))
-                        .build();
-                    // Params subscription is not working for some reason
-                    device
-                        .subscribe_params(&[ParamType::ROUTE, ParamType::PROP_INFO])
-                        .unwrap();
-                    device.add_listener(listener);
-                    devices.insert(id, device);
-                }
-            }
-        }))
-        .unwrap()
-}
-
-fn device_param_callback(_seq: i32, _type_: ParamType, index: u32, _next: u32, param: &PodRef) {
+fn print_route_info(index: usize, param: &PodObjectRef) {
     let mut info = DeviceRouteInfo::default();
 
-    if let BasicType::OBJECT(obj) = param.downcast().unwrap() {
-        let object_value = obj.value().unwrap();
-        if let ObjectType::OBJECT_PARAM_ROUTE(props) = object_value {
-            for prop in props {
-                if let Ok(prop) = prop.value() {
-                    match prop {
-                        ParamRouteType::PROPS(obj) => {
-                            if let Ok(ObjectType::OBJECT_PROPS(props)) = obj.value() {
-                                for prop in props {
-                                    if let Ok(prop_val) = prop.value() {
-                                        match prop_val {
-                                            ObjectPropType::CARD(name)
-                                            | ObjectPropType::CARD_NAME(name)
-                                            | ObjectPropType::DEVICE(name)
-                                            | ObjectPropType::DEVICE_NAME(name) => {
-                                                info.name = CString::from(name.value().unwrap())
-                                            }
-                                            ObjectPropType::CHANNEL_MAP(array) => {
-                                                info.channels = array.value().unwrap().collect()
-                                            }
-                                            ObjectPropType::CHANNEL_VOLUMES(array) => {
-                                                info.channel_volumes =
-                                                    array.value().unwrap().collect()
-                                            }
-                                            _ => {}
-                                        };
-                                    }
+    if let ObjectType::OBJECT_PARAM_ROUTE(props) = param.value().unwrap() {
+        for prop in props {
+            if let Ok(prop) = prop.value() {
+                match prop {
+                    ParamRouteType::PROPS(obj) => {
+                        if let Ok(ObjectType::OBJECT_PROPS(props)) = obj.value() {
+                            for prop in props {
+                                if let Ok(prop_val) = prop.value() {
+                                    match prop_val {
+                                        ObjectPropType::CARD(name)
+                                        | ObjectPropType::CARD_NAME(name)
+                                        | ObjectPropType::DEVICE(name)
+                                        | ObjectPropType::DEVICE_NAME(name) => {
+                                            info.name = CString::from(name.value().unwrap())
+                                        }
+                                        ObjectPropType::CHANNEL_MAP(array) => {
+                                            info.channels = array.value().unwrap().collect()
+                                        }
+                                        ObjectPropType::CHANNEL_VOLUMES(array) => {
+                                            info.channel_volumes = array.value().unwrap().collect()
+                                        }
+                                        _ => {}
+                                    };
                                 }
                             }
                         }
-                        ParamRouteType::NAME(name) => {
-                            info.name = CString::from(name.value().unwrap());
-                        }
-                        ParamRouteType::DESCRIPTION(name) => {
-                            info.description = CString::from(name.value().unwrap());
-                        }
-                        _ => (),
                     }
+                    ParamRouteType::NAME(name) => {
+                        info.name = CString::from(name.value().unwrap());
+                    }
+                    ParamRouteType::DESCRIPTION(name) => {
+                        info.description = CString::from(name.value().unwrap());
+                    }
+                    _ => (),
                 }
             }
         }
@@ -42,7 +42,7 @@ mod video_src {
     struct State {
         loop_: MainLoop,
         size: Option<RectangleRef>,
-        timeout_timer: TimerSource<'static, MainLoop>,
+        timeout_timer: TimerSource<MainLoop>,
 
         seq: u64,
         counter: u32,
@@ -5,7 +5,10 @@ use quote::__private::ext::RepToTokensExt;
 use quote::quote;
 use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
-use syn::{parse2, Fields, GenericParam, ItemEnum, LifetimeParam, Type, Variant};
+use syn::{
+    parse2, Expr, Fields, GenericArgument, GenericParam, ItemEnum, LifetimeParam, PathArguments,
+    Type, Variant,
+};
 
 struct ObjectTypeEnumInfo {
     ident_: Ident,
@@ -70,6 +73,48 @@ impl ObjectTypeEnumInfo {
             .collect()
     }
 
+    fn keys(&self) -> Vec<Expr> {
+        self.variants
+            .iter()
+            .map(|variant| {
+                variant
+                    .discriminant
+                    .as_ref()
+                    .map(|(_, expr)| expr.clone())
+                    .expect("Enum variants must have the key as discriminant")
+            })
+            .collect()
+    }
+
+    fn key_names(&self) -> Vec<String> {
+        self.variants
+            .iter()
+            .map(|variant| {
+                let mut name = String::new();
+                for (i, word) in variant.ident.to_string().split('_').enumerate() {
+                    let word = word.to_lowercase();
+                    let mut chars = word.chars();
+                    if let (true, Some(first)) = (i > 0, chars.next()) {
+                        name.extend(first.to_uppercase());
+                        name.push_str(chars.as_str());
+                    } else {
+                        name.push_str(word.as_str());
+                    }
+                }
+                name
+            })
+            .collect()
+    }
+
+    /// Keys and id types of the props with the id values, including choices and arrays of ids.
+    fn id_keys_and_types(&self) -> (Vec<Expr>, Vec<Type>) {
+        self.keys()
+            .into_iter()
+            .zip(self.struct_fields_types())
+            .filter_map(|(key, type_)| id_type(&type_).map(|id_type| (key, id_type)))
+            .unzip()
+    }
+
     fn struct_fields_types(&self) -> Vec<Type> {
         self.variants
             .iter()
@@ -82,6 +127,28 @@ impl ObjectTypeEnumInfo {
     }
 }
 
+fn id_type(type_: &Type) -> Option<Type> {
+    match type_ {
+        Type::Reference(ref_) => id_type(ref_.elem.deref()),
+        Type::Path(path) => {
+            let segment = path.path.segments.last()?;
+            let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return None;
+            };
+            args.args.iter().find_map(|arg| match arg {
+                GenericArgument::Type(Type::Path(arg_path))
+                    if segment.ident == "PodIdRef" && !arg_path.path.is_ident("u32") =>
+                {
+                    Some(Type::Path(arg_path.clone()))
+                }
+                GenericArgument::Type(arg_type) => id_type(arg_type),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
 pub fn object_type_impl(attr: TokenStream, input: TokenStream) -> TokenStream {
     let enum_info: ObjectTypeEnumInfo = parse2(input.clone()).unwrap();
     let object_type: Ident = parse2(attr).unwrap();
@@ -98,6 +165,9 @@ pub fn object_type_impl(attr: TokenStream, input: TokenStream) -> TokenStream {
     let struct_value_fields_idents = enum_info.struct_prop_value_fields_idents();
     let struct_flags_fields_idents = enum_info.struct_prop_flags_fields_idents();
     let struct_fields_types = enum_info.struct_fields_types();
+    let keys = enum_info.keys();
+    let key_names = enum_info.key_names();
+    let (id_keys, id_types) = enum_info.id_keys_and_types();
     let builder_fields_types: Vec<Type> = enum_info
         .struct_fields_types()
         .into_iter()
@@ -113,6 +183,31 @@ pub fn object_type_impl(attr: TokenStream, input: TokenStream) -> TokenStream {
     quote!(
         #input
 
+        impl<#lifetime> #enum_ident<#lifetime> {
+            /// Property keys with the readable names.
+            pub const KEY_NAMES: &'static [(u32, &'static str)] = &[#((#keys, #key_names)),*];
+
+            /// SPA type info name of the id value for the property key, if the property contains ids.
+            #[allow(unused_variables)]
+            pub fn id_value_name(key: u32, id: u32) -> Option<String> {
+                #(if key == #id_keys {
+                    let value = <#id_types as From<u32>>::from(id);
+                    return crate::spa::pod::id::PodIdType::type_info_name(&value);
+                })*
+                None
+            }
+
+            /// Id value for the property key with the SPA type info name or with the short name.
+            #[allow(unused_variables)]
+            pub fn id_value_from_name(key: u32, name: &str) -> Option<u32> {
+                #(if key == #id_keys {
+                    let value = <#id_types as crate::spa::pod::id::PodIdType>::from_type_info_name(name);
+                    return value.map(Into::into);
+                })*
+                None
+            }
+        }
+
         #[cfg(feature = "spa-pod-object-info")]
         #[derive(Default, Debug)]
         pub struct #info_struct_ident<#lifetime> {
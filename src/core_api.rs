@@ -26,12 +26,16 @@ pub mod factory;
 pub mod link;
 pub mod loop_;
 pub mod main_loop;
+pub mod metadata;
+pub mod module;
 pub mod node;
 pub mod permissions;
 pub mod port;
+pub mod profiler;
 pub mod properties;
 pub mod proxy;
 pub mod registry;
+pub mod thread_loop;
 pub mod type_info;
 
 pub const PW_ID_ANY: u32 = SPA_ID_INVALID;
@@ -135,6 +139,46 @@ impl PipeWire {
         unsafe { pw_sys::pw_check_option(option.as_ptr(), value.as_ptr()) }
     }
 
+    /// Version of the running PipeWire library
+    pub fn get_library_version() -> &'static CStr {
+        unsafe { CStr::from_ptr(pw_sys::pw_get_library_version()) }
+    }
+
+    /// Whether the running PipeWire library version is at least the given version
+    #[cfg(feature = "v0_3_77")]
+    pub fn check_library_version(major: u32, minor: u32, micro: u32) -> bool {
+        unsafe { pw_sys::pw_check_library_version(major as i32, minor as i32, micro as i32) }
+    }
+
+    /// Whether the running PipeWire library version is at least the given version
+    #[cfg(not(feature = "v0_3_77"))]
+    pub fn check_library_version(major: u32, minor: u32, micro: u32) -> bool {
+        // pw_check_library_version is not available before 0.3.77.
+        // The version is parsed once, the check is used from the realtime callbacks
+        static VERSION: std::sync::OnceLock<(u32, u32, u32)> = std::sync::OnceLock::new();
+        let actual = VERSION.get_or_init(|| {
+            let mut parts = Self::get_library_version()
+                .to_bytes()
+                .split(|byte| *byte == b'.')
+                .map(|part| {
+                    part.iter().take_while(|byte| byte.is_ascii_digit()).fold(
+                        0u32,
+                        |value, digit| {
+                            value
+                                .saturating_mul(10)
+                                .saturating_add((digit - b'0') as u32)
+                        },
+                    )
+                });
+            (
+                parts.next().unwrap_or(0),
+                parts.next().unwrap_or(0),
+                parts.next().unwrap_or(0),
+            )
+        });
+        *actual >= (major, minor, micro)
+    }
+
     /// Get reversed [Direction]
     pub fn direction_reverse(direction: &Direction) -> Direction {
         Direction::from_raw(unsafe { pw_sys::pw_direction_reverse(*direction.as_raw()) })
@@ -193,6 +237,20 @@ impl PipeWire {
     }
 }
 
+/// Fail with [crate::Error::LibraryVersionTooLow] if the running library is older than required.
+pub(crate) fn require_library_version(major: u32, minor: u32, micro: u32) -> crate::Result<()> {
+    if PipeWire::check_library_version(major, minor, micro) {
+        Ok(())
+    } else {
+        let required = format!("{}.{}.{}", major, minor, micro);
+        let actual = PipeWire::get_library_version().to_string_lossy();
+        Err(crate::Error::LibraryVersionTooLow(
+            required,
+            actual.into_owned(),
+        ))
+    }
+}
+
 impl Drop for PipeWire {
     fn drop(&mut self) {
         unsafe {
@@ -4,15 +4,19 @@
 
 //! PipeWire [Client](https://docs.pipewire.org/group__pw__client.html) bindings.
 //!
+use std::ffi::CStr;
 use std::pin::Pin;
 
 use pipewire_wrapper_proc_macro::{interface, proxy_wrapper, RawWrapper};
 
 use crate::core_api::client::events::ClientEvents;
 use crate::core_api::core::Core;
+use crate::core_api::permissions::ObjectPermissions;
 use crate::core_api::proxy::{Proxy, ProxyRef};
 use crate::core_api::registry::restricted::RegistryBind;
+use crate::i32_as_void_result;
 use crate::listeners::{AddListener, Listeners, OwnListeners};
+use crate::spa::dict::DictRef;
 use crate::spa_interface_call;
 use crate::wrapper::{RawWrapper, Wrapper};
 
@@ -28,6 +32,53 @@ pub struct ClientRef {
     raw: pw_sys::pw_client,
 }
 
+impl ClientRef {
+    /// Send an error to a client.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the global id to report the error on
+    /// * `res` - an errno style error code
+    /// * `message` - an error string
+    pub fn error(&self, id: u32, res: i32, message: &CStr) -> crate::Result<()> {
+        let result = unsafe { spa_interface_call!(self, error, id, res, message.as_ptr())? };
+        i32_as_void_result(result)
+    }
+
+    /// Update client properties.
+    pub fn update_properties(&self, props: &DictRef) -> crate::Result<()> {
+        let result = unsafe { spa_interface_call!(self, update_properties, props.as_raw_ptr())? };
+        i32_as_void_result(result)
+    }
+
+    /// Get client permissions.
+    /// A permissions event will be emitted with the permissions.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - the first index to query, 0 for first
+    /// * `num` - the maximum number of items to get
+    pub fn get_permissions(&self, index: u32, num: u32) -> crate::Result<()> {
+        let result = unsafe { spa_interface_call!(self, get_permissions, index, num)? };
+        i32_as_void_result(result)
+    }
+
+    /// Manage the permissions of the global objects for this client.
+    /// Update the permissions of the global objects using the provided array with permissions.
+    /// Globals can use the default permissions or can have specific permissions assigned to them.
+    pub fn update_permissions(&self, permissions: &[ObjectPermissions]) -> crate::Result<()> {
+        let result = unsafe {
+            spa_interface_call!(
+                self,
+                update_permissions,
+                permissions.len() as u32,
+                permissions.as_ptr().cast()
+            )?
+        };
+        i32_as_void_result(result)
+    }
+}
+
 impl AddListener for ClientRef {
     type Events = ClientEvents;
 
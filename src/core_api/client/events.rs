@@ -13,7 +13,7 @@ use pipewire_wrapper_proc_macro::{RawWrapper, Wrapper};
 
 use crate::core_api::client::info::ClientInfoRef;
 use crate::core_api::client::ClientRef;
-use crate::core_api::permissions::Permissions;
+use crate::core_api::permissions::ObjectPermissions;
 use crate::events_builder_build;
 use crate::spa::interface::Hook;
 use crate::wrapper::RawWrapper;
@@ -26,7 +26,7 @@ pub struct ClientEventsRef {
 }
 
 pub type InfoCallback = Box<dyn for<'a> FnMut(&'a ClientInfoRef)>;
-pub type PermissionsCallback = Box<dyn for<'a> FnMut(u32, &'a [Permissions])>;
+pub type PermissionsCallback = Box<dyn for<'a> FnMut(u32, &'a [ObjectPermissions])>;
 
 #[derive(Wrapper, Builder)]
 #[builder(setter(skip, strip_option), build_fn(skip), pattern = "owned")]
@@ -18,6 +18,9 @@ use crate::core_api::context::events::ContextEvents;
 use crate::core_api::loop_::LoopRef;
 use crate::core_api::main_loop::{MainLoop, MainLoopRef};
 use crate::core_api::properties::{Properties, PropertiesRef};
+use crate::core_api::require_library_version;
+use crate::core_api::thread_loop::ThreadLoop;
+use crate::impl_api::data_loop::DataLoopRef;
 use crate::impl_api::global::GlobalRef;
 use crate::impl_api::work_queue::WorkQueueRef;
 use crate::listeners::AddListener;
@@ -63,7 +66,23 @@ pub struct InnerContext {
     #[raw_wrapper]
     ref_: NonNull<ContextRef>,
 
-    main_loop: MainLoop,
+    loop_: ContextLoop,
+}
+
+/// Loop that runs the [Context] objects.
+#[derive(Debug)]
+enum ContextLoop {
+    Main(MainLoop),
+    Thread(ThreadLoop),
+}
+
+impl ContextLoop {
+    fn get_loop(&self) -> &LoopRef {
+        match self {
+            ContextLoop::Main(main_loop) => main_loop.get_loop(),
+            ContextLoop::Thread(thread_loop) => thread_loop.get_loop(),
+        }
+    }
 }
 
 impl Drop for InnerContext {
@@ -73,25 +92,45 @@ impl Drop for InnerContext {
 }
 
 impl InnerContext {
-    fn new(main_loop: MainLoop, properties: Properties) -> crate::Result<Self> {
+    fn new(loop_: ContextLoop, properties: Properties) -> crate::Result<Self> {
         let ptr = unsafe {
-            pw_sys::pw_context_new(main_loop.get_loop().as_raw_ptr(), properties.into_raw(), 0)
+            pw_sys::pw_context_new(loop_.get_loop().as_raw_ptr(), properties.into_raw(), 0)
         };
         Ok(Self {
             ref_: new_instance_raw_wrapper(ptr)?,
-            main_loop,
+            loop_,
         })
     }
 
     /// Main loop
+    ///
+    /// # Panics
+    ///
+    /// If the context is created by [Context::new_with_thread_loop],
+    /// use [InnerContext::thread_loop] for such contexts.
     pub fn main_loop(&self) -> &MainLoop {
-        &self.main_loop
+        match &self.loop_ {
+            ContextLoop::Main(main_loop) => main_loop,
+            ContextLoop::Thread(_) => panic!("Context runs on a ThreadLoop"),
+        }
+    }
+
+    /// Thread loop, [None] if the context runs on a [MainLoop]
+    pub fn thread_loop(&self) -> Option<&ThreadLoop> {
+        match &self.loop_ {
+            ContextLoop::Main(_) => None,
+            ContextLoop::Thread(thread_loop) => Some(thread_loop),
+        }
     }
 }
 
 impl Default for InnerContext {
     fn default() -> Self {
-        Self::new(MainLoop::default(), Properties::default()).unwrap()
+        Self::new(
+            ContextLoop::Main(MainLoop::default()),
+            Properties::default(),
+        )
+        .unwrap()
     }
 }
 
@@ -133,10 +172,14 @@ impl ContextRef {
         unsafe { LoopRef::from_raw_ptr(pw_sys::pw_context_get_main_loop(self.as_raw_ptr())) }
     }
 
-    // Since 0.3.56
-    // pub fn data_loop(&self) -> &DataLoopRef {
-    //     unsafe { DataLoopRef::from_raw_ptr(pw_sys::pw_context_get_data_loop(self.as_raw_ptr())) }
-    // }
+    /// Data loop of the context, available since PipeWire 0.3.56.
+    #[cfg(feature = "v0_3_56")]
+    pub fn get_data_loop(&self) -> crate::Result<&DataLoopRef> {
+        require_library_version(0, 3, 56)?;
+        Ok(unsafe {
+            DataLoopRef::from_raw_ptr(pw_sys::pw_context_get_data_loop(self.as_raw_ptr()))
+        })
+    }
 
     /// Work queue
     pub fn get_work_queue(&self) -> &WorkQueueRef {
@@ -235,7 +278,26 @@ impl Context {
     /// * `main_loop` - main loop
     /// * `properties` - extra properties for the context
     pub fn new(main_loop: MainLoop, properties: Properties) -> crate::Result<Self> {
-        let inner = InnerContext::new(main_loop, properties)?;
+        let inner = InnerContext::new(ContextLoop::Main(main_loop), properties)?;
+        Ok(Self {
+            inner: Rc::new(inner),
+        })
+    }
+
+    /// Creates a new [Context] for the given [ThreadLoop].
+    /// The context and all the objects created from it must be accessed with the loop lock held,
+    /// see [ThreadLoopRef::lock_guard](crate::core_api::thread_loop::ThreadLoopRef::lock_guard).
+    /// The loop is available with [InnerContext::thread_loop].
+    ///
+    /// # Arguments
+    ///
+    /// * `thread_loop` - thread loop
+    /// * `properties` - extra properties for the context
+    pub fn new_with_thread_loop(
+        thread_loop: ThreadLoop,
+        properties: Properties,
+    ) -> crate::Result<Self> {
+        let inner = InnerContext::new(ContextLoop::Thread(thread_loop), properties)?;
         Ok(Self {
             inner: Rc::new(inner),
         })
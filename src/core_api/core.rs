@@ -4,16 +4,18 @@
 
 //! PipeWire [Core](https://docs.pipewire.org/group__pw__core.html) bindings.
 //!
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::ops::Deref;
 use std::pin::Pin;
 use std::ptr::NonNull;
 use std::rc::Rc;
+use std::sync::Mutex;
 
 use pipewire_wrapper_proc_macro::{interface, RawWrapper, Wrapper};
 
 use crate::core_api::context::Context;
-use crate::core_api::core::events::CoreEvents;
+use crate::core_api::core::events::{CoreEvents, CoreEventsBuilder};
+use crate::core_api::loop_::executor::LoopFuture;
 use crate::core_api::properties::Properties;
 use crate::core_api::proxy::{Proxied, ProxyRef};
 use crate::core_api::registry::restricted::RegistryBind;
@@ -23,7 +25,7 @@ use crate::listeners::{AddListener, OwnListeners};
 use crate::spa::dict::DictRef;
 use crate::spa_interface_call;
 use crate::wrapper::{RawWrapper, Wrapper};
-use crate::{i32_as_void_result, new_instance_raw_wrapper, raw_wrapper};
+use crate::{i32_as_result, i32_as_void_result, new_instance_raw_wrapper, raw_wrapper};
 
 pub mod events;
 pub mod info;
@@ -91,9 +93,12 @@ impl CoreRef {
         i32_as_void_result(result)
     }
 
-    pub fn sync(&self, id: u32, seq: i32) -> crate::Result<()> {
+    /// Do server roundtrip.
+    /// Returns the sequence number that will be reported in the done event for the given `id`,
+    /// the `seq` argument is ignored by the native protocol.
+    pub fn sync(&self, id: u32, seq: i32) -> crate::Result<i32> {
         let result = spa_interface_call!(self, sync, id, seq)?;
-        i32_as_void_result(result)
+        i32_as_result(result, result)
     }
 
     pub fn pong(&self, id: u32, seq: i32) -> crate::Result<()> {
@@ -157,6 +162,40 @@ impl AddListener for CoreRef {
     }
 }
 
+/// Core error event, see [CoreEventsBuilder::error]
+#[derive(Clone, Debug)]
+pub struct CoreError {
+    id: u32,
+    seq: i32,
+    res: i32,
+    message: CString,
+}
+
+impl CoreError {
+    /// Id of the object that produced the error
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+    /// Sequence number of the request that caused the error
+    pub fn seq(&self) -> i32 {
+        self.seq
+    }
+    /// Negative errno style error code
+    pub fn res(&self) -> i32 {
+        self.res
+    }
+    pub fn message(&self) -> &CString {
+        &self.message
+    }
+}
+
+#[derive(Default)]
+struct RoundtripState {
+    seq: Option<i32>,
+    done: bool,
+    errors: Vec<CoreError>,
+}
+
 impl Deref for Core {
     type Target = InnerCore;
 
@@ -209,4 +248,118 @@ impl Core {
         )?;
         Ok(T::from_ref(self.clone(), proxy))
     }
+
+    /// Call the callback when the server has processed all the requests sent before this call.
+    /// The callback is called at most once from the loop.
+    ///
+    /// Returns the listener that must be kept alive until the callback is called.
+    pub fn sync_then<F>(&self, callback: F) -> crate::Result<Pin<Box<CoreEvents>>>
+    where
+        F: FnOnce() + 'static,
+    {
+        let pending_seq = Rc::new(Mutex::new(None));
+        let mut callback = Some(callback);
+        let listener = self.add_listener(
+            CoreEventsBuilder::default()
+                .done(Box::new({
+                    let pending_seq = pending_seq.clone();
+                    move |id, seq| {
+                        if id == pw_sys::PW_ID_CORE && *pending_seq.lock().unwrap() == Some(seq) {
+                            if let Some(callback) = callback.take() {
+                                callback();
+                            }
+                        }
+                    }
+                }))
+                .build(),
+        );
+        let seq = self.as_ref().sync(pw_sys::PW_ID_CORE, 0)?;
+        *pending_seq.lock().unwrap() = Some(seq);
+        Ok(listener)
+    }
+
+    /// Future that is resolved when the server has processed all the requests sent before this call.
+    /// See [Core::sync_then]
+    pub fn sync_future(&self) -> crate::Result<LoopFuture<()>> {
+        let (sender, future) = LoopFuture::new();
+        let listener = self.sync_then(move || sender.send(()))?;
+        Ok(future.keep(listener))
+    }
+
+    /// Block until the server has processed all the requests sent before this call.
+    /// The main loop of the [Context] is iterated until the done event is received,
+    /// so all the events that are emitted in the meantime are dispatched to their listeners.
+    /// Should not be called from the loop callbacks.
+    ///
+    /// For the [Context] on a [ThreadLoop](crate::core_api::thread_loop::ThreadLoop)
+    /// the loop lock must be held, the call waits for the loop thread to receive the done event.
+    ///
+    /// Returns the core error events received before the roundtrip finished.
+    /// An error for the core object itself, f.e. the broken connection, interrupts the roundtrip.
+    pub fn roundtrip(&self) -> crate::Result<Vec<CoreError>> {
+        let state = Rc::new(Mutex::new(RoundtripState::default()));
+        let _listener = self.add_listener(
+            CoreEventsBuilder::default()
+                .done(Box::new({
+                    let state = state.clone();
+                    let thread_loop = self.context().thread_loop().cloned();
+                    move |id, seq| {
+                        let mut state = state.lock().unwrap();
+                        if id == pw_sys::PW_ID_CORE && state.seq == Some(seq) {
+                            state.done = true;
+                            if let Some(thread_loop) = &thread_loop {
+                                thread_loop.signal(false);
+                            }
+                        }
+                    }
+                }))
+                .error(Box::new({
+                    let state = state.clone();
+                    let thread_loop = self.context().thread_loop().cloned();
+                    move |id, seq, res, message| {
+                        let mut state = state.lock().unwrap();
+                        if id == pw_sys::PW_ID_CORE {
+                            state.done = true;
+                            if let Some(thread_loop) = &thread_loop {
+                                thread_loop.signal(false);
+                            }
+                        }
+                        state.errors.push(CoreError {
+                            id,
+                            seq,
+                            res,
+                            message: CString::from(message),
+                        });
+                    }
+                }))
+                .build(),
+        );
+        let seq = self.as_ref().sync(pw_sys::PW_ID_CORE, 0)?;
+        state.lock().unwrap().seq = Some(seq);
+
+        if let Some(thread_loop) = self.context().thread_loop() {
+            if thread_loop.in_thread() {
+                return Err(crate::Error::ErrorMessage(
+                    "Roundtrip cannot wait inside the thread loop",
+                ));
+            }
+            loop {
+                let mut state = state.lock().unwrap();
+                if state.done {
+                    return Ok(std::mem::take(&mut state.errors));
+                }
+                drop(state);
+                thread_loop.wait();
+            }
+        }
+        for _ in self.context().main_loop().iter(-1) {
+            let mut state = state.lock().unwrap();
+            if state.done {
+                return Ok(std::mem::take(&mut state.errors));
+            }
+        }
+        Err(crate::Error::ErrorMessage(
+            "Loop iteration failed before the roundtrip is done",
+        ))
+    }
 }
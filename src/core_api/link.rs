@@ -4,10 +4,13 @@
 
 //! PipeWire [Link](https://docs.pipewire.org/group__pw__link.html) bindings.
 //!
+use std::ffi::{CStr, CString};
 use std::pin::Pin;
 
 use pipewire_wrapper_proc_macro::{interface, proxy_wrapper, RawWrapper};
 
+use crate::core_api::properties::{key, Properties};
+use crate::listeners::ListenerId;
 use crate::wrapper::RawWrapper;
 use crate::{
     listeners::{AddListener, Listeners, OwnListeners},
@@ -21,7 +24,8 @@ use super::{
     registry::restricted::RegistryBind,
 };
 
-use self::events::LinkEvents;
+use self::events::{LinkEvents, LinkEventsBuilder};
+use self::info::{ChangeMask, LinkState};
 
 pub mod events;
 pub mod info;
@@ -52,6 +56,38 @@ impl AddListener for LinkRef {
     }
 }
 
+const LINK_FACTORY_NAME: &[u8] = b"link-factory\0";
+
+/// Link side, used to create a new [Link].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkEndpoint {
+    /// Node id, the port is selected by the link factory
+    Node(u32),
+    /// Port id
+    Port(u32),
+    /// Node id and port id
+    NodePort(u32, u32),
+}
+
+impl LinkEndpoint {
+    fn node_id(&self) -> Option<u32> {
+        match self {
+            LinkEndpoint::Node(node) | LinkEndpoint::NodePort(node, _) => Some(*node),
+            LinkEndpoint::Port(_) => None,
+        }
+    }
+
+    fn port_id(&self) -> Option<u32> {
+        match self {
+            LinkEndpoint::Port(port) | LinkEndpoint::NodePort(_, port) => Some(*port),
+            LinkEndpoint::Node(_) => None,
+        }
+    }
+}
+
+/// Link state callback, the error message is provided for the [LinkState::ERROR] state.
+pub type StateCallback = Box<dyn for<'a> FnMut(LinkState, Option<&'a CStr>)>;
+
 #[derive(Clone, Debug)]
 #[proxy_wrapper(LinkRef)]
 pub struct Link {
@@ -60,6 +96,82 @@ pub struct Link {
     listeners: Listeners<Pin<Box<LinkEvents>>>,
 }
 
+impl Link {
+    /// Create a new link with the `link-factory`.
+    ///
+    /// # Arguments
+    ///
+    /// * `core` - [Core]
+    /// * `output` - output node or port
+    /// * `input` - input node or port
+    /// * `linger` - keep the link when the client is disconnected
+    /// * `props` - additional link properties
+    pub fn create(
+        core: &Core,
+        output: LinkEndpoint,
+        input: LinkEndpoint,
+        linger: bool,
+        props: Properties,
+    ) -> crate::Result<Self> {
+        Self::set_endpoint_props(
+            &props,
+            output,
+            pw_sys::PW_KEY_LINK_OUTPUT_NODE,
+            pw_sys::PW_KEY_LINK_OUTPUT_PORT,
+        );
+        Self::set_endpoint_props(
+            &props,
+            input,
+            pw_sys::PW_KEY_LINK_INPUT_NODE,
+            pw_sys::PW_KEY_LINK_INPUT_PORT,
+        );
+        props.set(
+            key(pw_sys::PW_KEY_OBJECT_LINGER),
+            key(if linger {
+                &b"true\0"[..]
+            } else {
+                &b"false\0"[..]
+            }),
+        );
+        core.create_object(
+            key(LINK_FACTORY_NAME),
+            pw_sys::PW_VERSION_LINK,
+            props.dict(),
+        )
+    }
+
+    fn set_endpoint_props(
+        props: &Properties,
+        endpoint: LinkEndpoint,
+        node_key: &[u8],
+        port_key: &[u8],
+    ) {
+        if let Some(node) = endpoint.node_id() {
+            let value = CString::new(node.to_string()).unwrap();
+            props.set(key(node_key), &value);
+        }
+        if let Some(port) = endpoint.port_id() {
+            let value = CString::new(port.to_string()).unwrap();
+            props.set(key(port_key), &value);
+        }
+    }
+
+    /// Add listener for the link state changes.
+    /// Use [events::LinkEventsChannelBuilder] to receive the state changes via channel.
+    ///
+    /// Returns [ListenerId] that can be used to remove the listener.
+    pub fn add_state_listener(&self, mut callback: StateCallback) -> ListenerId {
+        let events = LinkEventsBuilder::default()
+            .info(Box::new(move |info| {
+                if info.change_mask().contains(ChangeMask::STATE) {
+                    callback(info.state(), info.error());
+                }
+            }))
+            .build();
+        self.add_listener(events)
+    }
+}
+
 impl RegistryBind for Link {
     fn from_ref(core: Core, ref_: &ProxyRef) -> Self {
         Self {
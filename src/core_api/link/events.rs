@@ -5,6 +5,7 @@ use std::ffi::CStr;
 use std::fmt::{Debug, Formatter};
 use std::pin::Pin;
 use std::ptr::NonNull;
+use std::sync::mpsc;
 
 use derive_builder::Builder;
 use pw_sys::{pw_link_events, pw_link_info};
@@ -12,13 +13,16 @@ use spa_sys::spa_pod;
 
 use pipewire_wrapper_proc_macro::{RawWrapper, Wrapper};
 
-use crate::core_api::link::info::LinkInfoRef;
+use crate::core_api::link::info::{LinkInfo, LinkInfoRef};
 use crate::core_api::link::LinkRef;
-use crate::events_builder_build;
+use crate::core_api::loop_;
+use crate::core_api::loop_::channel::{Receiver, Sender};
+use crate::core_api::loop_::Loop;
 use crate::spa::interface::Hook;
 use crate::spa::param::{ParamInfoRef, ParamType};
 use crate::spa::pod::PodRef;
 use crate::wrapper::RawWrapper;
+use crate::{events_builder_build, events_channel_builder};
 
 #[derive(RawWrapper, Debug)]
 #[repr(transparent)]
@@ -60,7 +64,23 @@ impl LinkEvents {
     }
 }
 
-// todo: channel builder
+#[derive(Clone, Debug)]
+pub enum LinkEventType {
+    Info(LinkInfo),
+}
+
+impl<L: Loop> LinkEventsChannelBuilder<L> {
+    fn info_send(sender: Sender<LinkEventType, L>) -> InfoCallback {
+        Box::new(move |i| {
+            sender.send(LinkEventType::Info(i.into()));
+        })
+    }
+}
+
+events_channel_builder! {
+    Link,
+    info => info_send,
+}
 
 impl LinkEventsBuilder {
     events_builder_build! {
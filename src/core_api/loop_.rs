@@ -4,12 +4,16 @@
 
 //! PipeWire [Loop](https://docs.pipewire.org/group__pw__loop.html) bindings.
 //!
-use std::os::fd::RawFd;
+use std::future::Future;
+use std::os::fd::{OwnedFd, RawFd};
+use std::ptr::NonNull;
+use std::sync::Mutex;
 use std::time::Duration;
 
 use crate::core_api::loop_::restricted::AsLoopRef;
 use pipewire_wrapper_proc_macro::RawWrapper;
 
+use crate::core_api::loop_::executor::LocalTask;
 use crate::spa;
 use crate::spa::loop_::utils::LoopUtilsRef;
 use crate::spa::loop_::LoopControlRef;
@@ -17,6 +21,9 @@ use crate::spa::system::SystemRef;
 use crate::wrapper::*;
 
 pub mod channel;
+pub mod executor;
+#[cfg(feature = "reactor")]
+pub mod reactor;
 
 #[derive(RawWrapper, Debug)]
 #[repr(transparent)]
@@ -151,12 +158,13 @@ impl<T: AsLoopRef> spa::loop_::restricted::AsLoopRef for T {
 }
 
 pub trait Loop: AsLoopRef + Clone + 'static {
+    /// Add the IO source, the fd is closed when the source is dropped.
     fn add_io<F>(
         &self,
-        fd: RawFd,
+        fd: OwnedFd,
         mask: u32,
         callback: F,
-    ) -> crate::Result<spa::loop_::IOSource<'static, Self>>
+    ) -> crate::Result<spa::loop_::IOSource<Self>>
     where
         F: FnMut(RawFd, u32) + 'static,
     {
@@ -164,11 +172,7 @@ pub trait Loop: AsLoopRef + Clone + 'static {
             .add_io(self.clone(), fd, mask, Box::new(callback))
     }
 
-    fn add_idle<F>(
-        &self,
-        enabled: bool,
-        callback: F,
-    ) -> crate::Result<spa::loop_::IdleSource<'static, Self>>
+    fn add_idle<F>(&self, enabled: bool, callback: F) -> crate::Result<spa::loop_::IdleSource<Self>>
     where
         F: FnMut() + 'static,
     {
@@ -176,14 +180,14 @@ pub trait Loop: AsLoopRef + Clone + 'static {
             .add_idle(self.clone(), enabled, Box::new(callback))
     }
 
-    fn add_event<F>(&self, callback: F) -> crate::Result<spa::loop_::EventSource<'static, Self>>
+    fn add_event<F>(&self, callback: F) -> crate::Result<spa::loop_::EventSource<Self>>
     where
         F: FnMut(u64) + 'static,
     {
         self.utils().add_event(self.clone(), Box::new(callback))
     }
 
-    fn add_timer<F>(&self, callback: F) -> crate::Result<spa::loop_::TimerSource<'static, Self>>
+    fn add_timer<F>(&self, callback: F) -> crate::Result<spa::loop_::TimerSource<Self>>
     where
         F: FnMut(u64) + 'static,
     {
@@ -194,7 +198,7 @@ pub trait Loop: AsLoopRef + Clone + 'static {
         &self,
         signal_number: i32,
         callback: F,
-    ) -> crate::Result<spa::loop_::SignalSource<'static, Self>>
+    ) -> crate::Result<spa::loop_::SignalSource<Self>>
     where
         F: FnMut(i32) + 'static,
     {
@@ -202,6 +206,18 @@ pub trait Loop: AsLoopRef + Clone + 'static {
             .add_signal(self.clone(), signal_number, Box::new(callback))
     }
 
+    /// Spawn the future on this loop.
+    /// The future is polled from the loop callbacks in the loop thread.
+    ///
+    /// Returns the [LocalTask] handle, the task is cancelled when the handle is dropped.
+    fn spawn_local<F>(&self, future: F) -> crate::Result<LocalTask<F::Output, Self>>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        LocalTask::spawn(self, future)
+    }
+
     /// Must be called inside loop or when loop is not running
     fn destroy_source(&self, source: &spa::loop_::SourceRef) -> crate::Result<()> {
         self.utils().destroy_source(source)
@@ -210,25 +226,72 @@ pub trait Loop: AsLoopRef + Clone + 'static {
 
 impl<T: AsLoopRef + Clone + 'static> Loop for T {}
 
-impl<T: Loop> spa::loop_::IOSource<'static, T> {
+/// Thread safe handle to signal the [spa::loop_::EventSource] from any thread.
+/// The handle must be cleared before the event source is removed,
+/// signals after that are ignored.
+#[derive(Debug, Default)]
+pub(crate) struct EventSignal {
+    target: Mutex<Option<EventSignalTarget>>,
+}
+
+#[derive(Debug)]
+struct EventSignalTarget {
+    utils: NonNull<LoopUtilsRef<LoopRef>>,
+    source: NonNull<spa::loop_::SourceRef>,
+}
+
+// Signaling the loop event is thread safe, the target is guarded by the mutex
+unsafe impl Send for EventSignal {}
+unsafe impl Sync for EventSignal {}
+
+impl EventSignal {
+    pub(crate) fn set<L: Loop>(&self, loop_: &L, event: &spa::loop_::EventSource<L>) {
+        *self.target.lock().unwrap() = Some(EventSignalTarget {
+            utils: NonNull::from(loop_.loop_().utils()),
+            source: NonNull::from(event.as_ref()),
+        });
+    }
+
+    pub(crate) fn clear(&self) {
+        *self.target.lock().unwrap() = None;
+    }
+
+    /// Signal the event source.
+    /// Returns false if the event source is not set.
+    pub(crate) fn signal(&self) -> crate::Result<bool> {
+        if let Some(target) = self.target.lock().unwrap().as_ref() {
+            unsafe {
+                target
+                    .utils
+                    .as_ref()
+                    .signal_source(target.source.as_ref())?;
+            }
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+}
+
+impl<T: Loop> spa::loop_::IOSource<T> {
     pub fn update(&self, mask: u32) -> crate::Result<()> {
         self.loop_.update_io(self, mask)
     }
 }
 
-impl<T: Loop> spa::loop_::IdleSource<'static, T> {
+impl<T: Loop> spa::loop_::IdleSource<T> {
     pub fn enable(&self, enabled: bool) -> crate::Result<()> {
         self.loop_.enable_idle(self, enabled)
     }
 }
 
-impl<T: Loop> spa::loop_::EventSource<'static, T> {
+impl<T: Loop> spa::loop_::EventSource<T> {
     pub fn signal(&self) -> crate::Result<()> {
         self.loop_.signal_event(self)
     }
 }
 
-impl<T: Loop> spa::loop_::TimerSource<'static, T> {
+impl<T: Loop> spa::loop_::TimerSource<T> {
     pub fn update(&self, value: Duration, interval: Duration, absolute: bool) -> crate::Result<()> {
         self.loop_.update_timer(self, value, interval, absolute)
     }
@@ -1,18 +1,19 @@
 /*
  * SPDX-License-Identifier: MIT
  */
-use std::fmt::Debug;
-use std::ops::DerefMut;
-use std::rc::Rc;
-use std::sync::{mpsc, Mutex};
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::sync::{mpsc, Arc};
 
-use crate::core_api::loop_::{Loop, LoopRef};
+use crate::core_api::loop_::{EventSignal, Loop};
 use crate::spa::loop_::EventSource;
 
 #[derive(Debug)]
 pub enum SendError<T> {
     SendError(mpsc::SendError<T>),
     CannotSignalEvent(crate::Error),
+    /// Bounded channel is full, returned by [Sender::try_send]
+    ChannelIsFull(T),
 }
 
 impl<T> From<SendError<T>> for crate::Error {
@@ -22,84 +23,242 @@ impl<T> From<SendError<T>> for crate::Error {
                 crate::Error::ErrorMessage("Receiver is disconnected, unable to send message")
             }
             SendError::CannotSignalEvent(e) => e,
+            SendError::ChannelIsFull(_) => {
+                crate::Error::ErrorMessage("Channel is full, unable to send message")
+            }
         }
     }
 }
 
+/// Channel that wakes up the loop when a message is sent.
+/// [Sender] is [Send] and [Sync], so the messages can be sent from any thread,
+/// [Receiver] is attached to the loop and receives the messages in the loop thread.
 pub struct LoopChannel<L: Loop> {
-    event: Option<EventSource<'static, L>>,
+    phantom: PhantomData<L>,
 }
 
 impl<L: Loop> LoopChannel<L> {
+    /// Channel with unlimited capacity.
     pub fn channel<T>() -> (Sender<T, L>, Receiver<T, L>) {
         Self::from_channel(mpsc::channel())
     }
 
+    /// Channel with the limited capacity.
+    /// [Sender::send] blocks and [Sender::try_send] fails when the channel is full.
+    pub fn bounded_channel<T>(capacity: usize) -> (Sender<T, L>, Receiver<T, L>) {
+        Self::from_sync_channel(mpsc::sync_channel(capacity))
+    }
+
     pub fn from_channel<T>(
         (sender, receiver): (mpsc::Sender<T>, mpsc::Receiver<T>),
     ) -> (Sender<T, L>, Receiver<T, L>) {
-        let channel = Rc::new(Mutex::new(LoopChannel { event: None }));
+        Self::from_parts(MessageSender::Unbounded(sender), receiver)
+    }
+
+    pub fn from_sync_channel<T>(
+        (sender, receiver): (mpsc::SyncSender<T>, mpsc::Receiver<T>),
+    ) -> (Sender<T, L>, Receiver<T, L>) {
+        Self::from_parts(MessageSender::Bounded(sender), receiver)
+    }
+
+    fn from_parts<T>(
+        sender: MessageSender<T>,
+        receiver: mpsc::Receiver<T>,
+    ) -> (Sender<T, L>, Receiver<T, L>) {
+        let signal = Arc::new(EventSignal::default());
         (
             Sender {
-                channel: channel.clone(),
                 sender,
+                signal: signal.clone(),
+                phantom: PhantomData,
+            },
+            Receiver {
+                receiver,
+                signal,
+                phantom: PhantomData,
             },
-            Receiver { channel, receiver },
         )
     }
 }
 
-#[derive(Clone)]
+enum MessageSender<T> {
+    Unbounded(mpsc::Sender<T>),
+    Bounded(mpsc::SyncSender<T>),
+}
+
+impl<T> Clone for MessageSender<T> {
+    fn clone(&self) -> Self {
+        match self {
+            MessageSender::Unbounded(sender) => MessageSender::Unbounded(sender.clone()),
+            MessageSender::Bounded(sender) => MessageSender::Bounded(sender.clone()),
+        }
+    }
+}
+
+/// Sending half of the [LoopChannel].
 pub struct Sender<T, L: Loop> {
-    sender: mpsc::Sender<T>,
-    channel: Rc<Mutex<LoopChannel<L>>>,
+    sender: MessageSender<T>,
+    signal: Arc<EventSignal>,
+    phantom: PhantomData<fn(L)>,
 }
 
 impl<T, L: Loop> Sender<T, L> {
+    /// Send the message and wake up the attached receiver.
+    /// Blocks if the bounded channel is full.
     pub fn send(&self, value: T) -> Result<(), SendError<T>> {
-        let mut channel = self.channel.lock().unwrap();
-        self.sender
-            .send(value)
-            .map_err(|e| SendError::SendError(e))?;
-        if let LoopChannel { event: Some(event) } = channel.deref_mut() {
-            event
-                .signal()
-                .map_err(|e| SendError::CannotSignalEvent(e))?;
+        match &self.sender {
+            MessageSender::Unbounded(sender) => sender.send(value),
+            MessageSender::Bounded(sender) => sender.send(value),
+        }
+        .map_err(|e| SendError::SendError(e))?;
+        self.signal()
+    }
+
+    /// Send the message without blocking.
+    /// Returns [SendError::ChannelIsFull] if the bounded channel is full.
+    pub fn try_send(&self, value: T) -> Result<(), SendError<T>> {
+        match &self.sender {
+            MessageSender::Unbounded(sender) => {
+                sender.send(value).map_err(|e| SendError::SendError(e))?
+            }
+            MessageSender::Bounded(sender) => sender.try_send(value).map_err(|e| match e {
+                mpsc::TrySendError::Full(value) => SendError::ChannelIsFull(value),
+                mpsc::TrySendError::Disconnected(value) => {
+                    SendError::SendError(mpsc::SendError(value))
+                }
+            })?,
         }
+        self.signal()
+    }
+
+    fn signal(&self) -> Result<(), SendError<T>> {
+        self.signal
+            .signal()
+            .map_err(|e| SendError::CannotSignalEvent(e))?;
         Ok(())
     }
 
-    pub fn into_sender(self) -> mpsc::Sender<T> {
-        self.sender
+    /// Underlying sender of the unbounded channel, the receiver is not woken up by this sender.
+    /// Returns None for the bounded channel.
+    pub fn into_sender(self) -> Option<mpsc::Sender<T>> {
+        match self.sender {
+            MessageSender::Unbounded(sender) => Some(sender),
+            MessageSender::Bounded(_) => None,
+        }
     }
 
+    /// Stop waking up the attached receiver.
+    /// The messages are still delivered to the receiver, but they are received only
+    /// when the receiver is woken up by other means.
     pub fn detach(&self) {
-        let mut channel = self.channel.lock().unwrap();
-        channel.event = None;
+        self.signal.clear();
     }
 }
 
+impl<T, L: Loop> Clone for Sender<T, L> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            signal: self.signal.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, L: Loop> Debug for Sender<T, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sender")
+            .field("signal", &self.signal)
+            .finish()
+    }
+}
+
+/// Receiving half of the [LoopChannel].
 pub struct Receiver<T, L: Loop> {
     receiver: mpsc::Receiver<T>,
-    channel: Rc<Mutex<LoopChannel<L>>>,
+    signal: Arc<EventSignal>,
+    phantom: PhantomData<L>,
 }
 
 pub type ReceiverCallback<T> = Box<dyn FnMut(&mpsc::Receiver<T>)>;
 
 impl<T: 'static, L: Loop> Receiver<T, L> {
-    pub fn attach(self, loop_: &L, mut callback: ReceiverCallback<T>) -> crate::Result<()> {
-        let channel = self.channel.clone();
+    /// Attach the receiver to the loop.
+    /// The callback is called in the loop thread when new messages are sent.
+    ///
+    /// Returns [AttachedReceiver], the receiver is detached when it is dropped.
+    pub fn attach(
+        self,
+        loop_: &L,
+        mut callback: ReceiverCallback<T>,
+    ) -> crate::Result<AttachedReceiver<L>> {
+        let signal = self.signal.clone();
+        let receiver = self.receiver;
         let event = loop_.add_event(Box::new({
             move |_count| {
-                callback(&self.receiver);
+                callback(&receiver);
             }
         }))?;
-        let mut channel = channel.lock().unwrap();
-        channel.event = Some(event);
-        Ok(())
+        signal.set(loop_, &event);
+        // Receive the messages sent before attaching
+        event.signal()?;
+        Ok(AttachedReceiver { signal, event })
+    }
+
+    /// Attach the receiver to the loop, the callback is called for each received message.
+    ///
+    /// Returns [AttachedReceiver], the receiver is detached when it is dropped.
+    pub fn attach_each<F>(self, loop_: &L, mut callback: F) -> crate::Result<AttachedReceiver<L>>
+    where
+        F: FnMut(T) + 'static,
+    {
+        self.attach(
+            loop_,
+            Box::new(move |receiver| receiver.try_iter().for_each(&mut callback)),
+        )
+    }
+
+    /// Receive the pending message without blocking.
+    pub fn try_recv(&self) -> Result<T, mpsc::TryRecvError> {
+        self.receiver.try_recv()
+    }
+
+    /// Iterate over the pending messages without blocking.
+    pub fn try_iter(&self) -> mpsc::TryIter<T> {
+        self.receiver.try_iter()
     }
 
     pub fn into_receiver(self) -> mpsc::Receiver<T> {
         self.receiver
     }
 }
+
+impl<T, L: Loop> Debug for Receiver<T, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Receiver")
+            .field("signal", &self.signal)
+            .finish()
+    }
+}
+
+/// [Receiver] attached to the loop.
+/// Senders stop waking up the loop when it is dropped.
+#[must_use]
+pub struct AttachedReceiver<L: Loop> {
+    signal: Arc<EventSignal>,
+    event: EventSource<L>,
+}
+
+impl<L: Loop> Drop for AttachedReceiver<L> {
+    fn drop(&mut self) {
+        self.signal.clear();
+    }
+}
+
+impl<L: Loop> Debug for AttachedReceiver<L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttachedReceiver")
+            .field("event", &self.event)
+            .finish()
+    }
+}
@@ -0,0 +1,237 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Single-threaded executor that polls the futures on the loop.
+//!
+//! Each spawned task has its own [EventSource], the task waker signals the event
+//! and the future is polled from the event callback.
+//!
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+use crate::core_api::loop_::{EventSignal, Loop};
+use crate::spa::loop_::EventSource;
+
+type LocalFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+
+enum TaskState<T> {
+    Running(LocalFuture<T>),
+    Finished(T),
+    Taken,
+}
+
+struct TaskShared<T> {
+    state: Mutex<TaskState<T>>,
+    join_waker: Mutex<Option<Waker>>,
+    repoll: AtomicBool,
+}
+
+/// Task waker, signals the task event source.
+/// The late wakeups after the task is dropped are ignored.
+#[derive(Default)]
+struct TaskSignal(EventSignal);
+
+impl Wake for TaskSignal {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let _ = self.0.signal();
+    }
+}
+
+/// Handle of the task spawned with [Loop::spawn_local].
+/// The task is cancelled when the handle is dropped, so it should be kept until the task is finished.
+/// The handle is a [Future] itself and can be awaited by another task to get the task output.
+pub struct LocalTask<T, L: Loop> {
+    shared: Rc<TaskShared<T>>,
+    signal: Arc<TaskSignal>,
+    event: EventSource<L>,
+}
+
+impl<T: 'static, L: Loop> LocalTask<T, L> {
+    pub(crate) fn spawn<F>(loop_: &L, future: F) -> crate::Result<Self>
+    where
+        F: Future<Output = T> + 'static,
+    {
+        let shared = Rc::new(TaskShared {
+            state: Mutex::new(TaskState::Running(Box::pin(future))),
+            join_waker: Mutex::new(None),
+            repoll: AtomicBool::new(false),
+        });
+        let signal = Arc::new(TaskSignal::default());
+        let event = loop_.add_event({
+            let shared = shared.clone();
+            let waker = Waker::from(signal.clone());
+            move |_count| Self::poll_task(&shared, &waker)
+        })?;
+        signal.0.set(loop_, &event);
+        event.signal()?;
+        Ok(Self {
+            shared,
+            signal,
+            event,
+        })
+    }
+
+    fn poll_task(shared: &TaskShared<T>, waker: &Waker) {
+        // The task can be woken while it is polled, f.e. when the future iterates the loop
+        let mut state = match shared.state.try_lock() {
+            Ok(state) => state,
+            Err(_) => {
+                shared.repoll.store(true, Ordering::Relaxed);
+                return;
+            }
+        };
+        while let TaskState::Running(future) = &mut *state {
+            shared.repoll.store(false, Ordering::Relaxed);
+            if let Poll::Ready(output) = future.as_mut().poll(&mut Context::from_waker(waker)) {
+                *state = TaskState::Finished(output);
+                drop(state);
+                if let Some(join_waker) = shared.join_waker.lock().unwrap().take() {
+                    join_waker.wake();
+                }
+                return;
+            }
+            if !shared.repoll.load(Ordering::Relaxed) {
+                return;
+            }
+        }
+    }
+
+    /// Whether the task future is completed.
+    pub fn is_finished(&self) -> bool {
+        !matches!(*self.shared.state.lock().unwrap(), TaskState::Running(_))
+    }
+
+    /// Take the task output if the task is finished.
+    pub fn take_output(&self) -> Option<T> {
+        let mut state = self.shared.state.lock().unwrap();
+        match std::mem::replace(&mut *state, TaskState::Taken) {
+            TaskState::Finished(output) => Some(output),
+            other => {
+                *state = other;
+                None
+            }
+        }
+    }
+}
+
+impl<T: 'static, L: Loop> Future for LocalTask<T, L> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(output) = self.take_output() {
+            Poll::Ready(output)
+        } else {
+            *self.shared.join_waker.lock().unwrap() = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl<T, L: Loop> Drop for LocalTask<T, L> {
+    fn drop(&mut self) {
+        self.signal.0.clear();
+    }
+}
+
+impl<T, L: Loop> Debug for LocalTask<T, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalTask")
+            .field("event", &self.event)
+            .finish()
+    }
+}
+
+struct FutureSlot<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+    completed: bool,
+}
+
+/// Future that is resolved from the loop callbacks, f.e. the proxy events.
+/// Listeners that feed the future are kept alive until the future is dropped.
+pub struct LoopFuture<T> {
+    slot: Rc<Mutex<FutureSlot<T>>>,
+    listeners: Vec<Box<dyn Any>>,
+}
+
+/// Completes the paired [LoopFuture], only the first value is used.
+pub(crate) struct LoopFutureSender<T> {
+    slot: Rc<Mutex<FutureSlot<T>>>,
+}
+
+impl<T> LoopFuture<T> {
+    pub(crate) fn new() -> (LoopFutureSender<T>, Self) {
+        let slot = Rc::new(Mutex::new(FutureSlot {
+            value: None,
+            waker: None,
+            completed: false,
+        }));
+        (
+            LoopFutureSender { slot: slot.clone() },
+            Self {
+                slot,
+                listeners: Vec::new(),
+            },
+        )
+    }
+
+    /// Keep the listener alive while the future is alive.
+    pub(crate) fn keep<K: Any>(mut self, listener: K) -> Self {
+        self.listeners.push(Box::new(listener));
+        self
+    }
+}
+
+impl<T> LoopFutureSender<T> {
+    pub(crate) fn send(&self, value: T) {
+        let mut slot = self.slot.lock().unwrap();
+        if !slot.completed {
+            slot.completed = true;
+            slot.value = Some(value);
+            if let Some(waker) = slot.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T> Clone for LoopFutureSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            slot: self.slot.clone(),
+        }
+    }
+}
+
+impl<T> Future for LoopFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock().unwrap();
+        if let Some(value) = slot.value.take() {
+            Poll::Ready(value)
+        } else {
+            slot.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> Debug for LoopFuture<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoopFuture")
+            .field("listeners", &self.listeners.len())
+            .finish()
+    }
+}
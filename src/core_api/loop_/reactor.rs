@@ -0,0 +1,82 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Adapter to drive the loop from an external event loop.
+//!
+//! The loop fd becomes readable when the loop has pending events,
+//! so the external reactor should wait for it and call [LoopReactor::dispatch].
+//!
+//! # Examples
+//!
+//! ```no_run,ignore
+//! use tokio::io::unix::AsyncFd;
+//! use pipewire_wrapper::core_api::main_loop::MainLoop;
+//! use pipewire_wrapper::core_api::loop_::reactor::LoopReactor;
+//!
+//! let reactor = AsyncFd::new(LoopReactor::new(MainLoop::default())?)?;
+//! loop {
+//!     let mut guard = reactor.readable().await?;
+//!     guard.get_inner().dispatch()?;
+//!     guard.clear_ready();
+//! }
+//! ```
+//!
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, RawFd};
+
+use crate::core_api::loop_::restricted::AsLoopRef;
+use crate::core_api::loop_::Loop;
+use crate::i32_as_result;
+
+/// Loop driven by the external reactor.
+/// The loop is entered on creation and left on drop, so the reactor must be used
+/// from the thread where it was created.
+/// The loop must not be run or iterated by other means while the reactor is alive.
+#[derive(Debug)]
+pub struct LoopReactor<L: Loop> {
+    loop_: L,
+    fd: RawFd,
+}
+
+impl<L: Loop> LoopReactor<L> {
+    /// Create the reactor adapter and enter the loop.
+    pub fn new(loop_: L) -> crate::Result<Self> {
+        let control = loop_.loop_().control();
+        let fd = control.get_fd()?;
+        control.enter()?;
+        Ok(Self { loop_, fd })
+    }
+
+    /// Dispatch the pending events without blocking.
+    /// Should be called when the loop fd is readable.
+    ///
+    /// Returns the number of dispatched sources.
+    pub fn dispatch(&self) -> crate::Result<i32> {
+        let result = self.loop_.loop_().control().iterate(0)?;
+        i32_as_result(result, result)
+    }
+
+    /// Loop driven by the reactor
+    pub fn get_loop(&self) -> &L {
+        &self.loop_
+    }
+}
+
+impl<L: Loop> Drop for LoopReactor<L> {
+    fn drop(&mut self) {
+        let _ = self.loop_.loop_().control().leave();
+    }
+}
+
+impl<L: Loop> AsRawFd for LoopReactor<L> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl<L: Loop> AsFd for LoopReactor<L> {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        // The fd is owned by the loop, which is alive while the reactor is alive
+        unsafe { BorrowedFd::borrow_raw(self.fd) }
+    }
+}
@@ -0,0 +1,113 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+//! PipeWire [Metadata](https://docs.pipewire.org/group__pw__metadata.html) bindings.
+//!
+use std::ffi::CStr;
+use std::pin::Pin;
+use std::ptr::null;
+
+use pipewire_wrapper_proc_macro::{interface, proxy_wrapper, RawWrapper};
+
+use crate::core_api::core::Core;
+use crate::core_api::metadata::events::MetadataEvents;
+use crate::core_api::proxy::{Proxy, ProxyRef};
+use crate::core_api::registry::restricted::RegistryBind;
+use crate::i32_as_void_result;
+use crate::listeners::{AddListener, Listeners, OwnListeners};
+use crate::spa_interface_call;
+use crate::wrapper::{RawWrapper, Wrapper};
+
+pub mod events;
+
+/// Wrapper for the external [pw_sys::pw_metadata] value.
+/// Metadata is a key/value store attached to the subjects (object ids).
+#[derive(RawWrapper, Debug)]
+#[interface(methods=pw_sys::pw_metadata_methods, interface="Metadata")]
+#[repr(transparent)]
+pub struct MetadataRef {
+    #[raw]
+    raw: pw_sys::pw_metadata,
+}
+
+impl MetadataRef {
+    /// Set a metadata property.
+    /// Automatically emit property events for the subject and key when changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `subject` - the id of the global to associate the metadata with
+    /// * `key` - the key of the metadata
+    /// * `type_` - the type of the metadata, this can be blank
+    /// * `value` - the metadata value, `None` removes the property
+    pub fn set_property(
+        &self,
+        subject: u32,
+        key: &CStr,
+        type_: Option<&CStr>,
+        value: Option<&CStr>,
+    ) -> crate::Result<()> {
+        let result = unsafe {
+            spa_interface_call!(
+                self,
+                set_property,
+                subject,
+                key.as_ptr(),
+                type_.map_or(null(), |t| t.as_ptr()),
+                value.map_or(null(), |v| v.as_ptr())
+            )?
+        };
+        i32_as_void_result(result)
+    }
+
+    /// Clear all metadata
+    pub fn clear(&self) -> crate::Result<()> {
+        let result = unsafe { spa_interface_call!(self, clear)? };
+        i32_as_void_result(result)
+    }
+}
+
+impl AddListener for MetadataRef {
+    type Events = MetadataEvents;
+
+    fn add_listener(&self, events: Pin<Box<Self::Events>>) -> Pin<Box<Self::Events>> {
+        unsafe {
+            spa_interface_call!(
+                self,
+                add_listener,
+                events.hook().as_raw_ptr(),
+                events.as_raw_ptr(),
+                &*events as *const _ as *mut _
+            )
+        };
+
+        events
+    }
+}
+
+/// Wrapper for the Metadata proxy, can be obtained from the [crate::core_api::registry::Registry].
+#[derive(Clone, Debug)]
+#[proxy_wrapper(MetadataRef)]
+pub struct Metadata {
+    ref_: Proxy,
+
+    listeners: Listeners<Pin<Box<MetadataEvents>>>,
+}
+
+impl RegistryBind for Metadata {
+    fn from_ref(core: Core, ref_: &ProxyRef) -> Self {
+        Self {
+            ref_: Proxy::from_ref(core, ref_),
+            listeners: Listeners::default(),
+        }
+    }
+}
+
+impl OwnListeners for Metadata {
+    fn listeners(
+        &self,
+    ) -> &Listeners<Pin<Box<<<Self as Wrapper>::RawWrapperType as AddListener>::Events>>> {
+        &self.listeners
+    }
+}
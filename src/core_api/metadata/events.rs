@@ -0,0 +1,90 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+use std::ffi::CStr;
+use std::fmt::{Debug, Formatter};
+use std::pin::Pin;
+use std::ptr::NonNull;
+
+use derive_builder::Builder;
+use pw_sys::pw_metadata_events;
+
+use pipewire_wrapper_proc_macro::{RawWrapper, Wrapper};
+
+use crate::events_builder_build;
+use crate::spa::interface::Hook;
+use crate::wrapper::RawWrapper;
+
+#[derive(RawWrapper, Debug)]
+#[repr(transparent)]
+pub struct MetadataEventsRef {
+    #[raw]
+    raw: pw_sys::pw_metadata_events,
+}
+
+/// Property changed callback.
+/// Arguments are subject, key, type and value.
+/// Value is `None` when the property was removed, key is `None` when all properties were removed.
+pub type PropertyCallback =
+    Box<dyn for<'a> FnMut(u32, Option<&'a CStr>, Option<&'a CStr>, Option<&'a CStr>)>;
+
+#[derive(Wrapper, Builder)]
+#[builder(setter(skip, strip_option), build_fn(skip), pattern = "owned")]
+pub struct MetadataEvents {
+    #[raw_wrapper]
+    ref_: NonNull<MetadataEventsRef>,
+
+    raw: Pin<Box<MetadataEventsRef>>,
+    hook: Pin<Box<Hook>>,
+
+    #[builder(setter)]
+    property: Option<PropertyCallback>,
+}
+
+impl MetadataEvents {
+    unsafe extern "C" fn property_call(
+        data: *mut ::std::os::raw::c_void,
+        subject: u32,
+        key: *const ::std::os::raw::c_char,
+        type_: *const ::std::os::raw::c_char,
+        value: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int {
+        if let Some(events) = (data as *mut MetadataEvents).as_mut() {
+            if let Some(callback) = &mut events.property {
+                callback(
+                    subject,
+                    key.as_ref().map(|ptr| CStr::from_ptr(ptr)),
+                    type_.as_ref().map(|ptr| CStr::from_ptr(ptr)),
+                    value.as_ref().map(|ptr| CStr::from_ptr(ptr)),
+                );
+            }
+        }
+        0
+    }
+
+    pub fn hook(&self) -> &Pin<Box<Hook>> {
+        &self.hook
+    }
+
+    pub fn version(&self) -> u32 {
+        self.raw.raw.version
+    }
+}
+
+// todo: channel builder
+
+impl MetadataEventsBuilder {
+    events_builder_build! {
+        MetadataEvents,
+        pw_metadata_events,
+        property => property_call,
+    }
+}
+
+impl Debug for MetadataEvents {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetadataEvents")
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
@@ -0,0 +1,75 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+use std::fmt::{Debug, Formatter};
+use std::pin::Pin;
+use std::ptr::NonNull;
+
+use derive_builder::Builder;
+use pw_sys::{pw_module_events, pw_module_info};
+
+use pipewire_wrapper_proc_macro::{RawWrapper, Wrapper};
+
+use crate::core_api::module::info::ModuleInfoRef;
+use crate::core_api::module::ModuleRef;
+use crate::events_builder_build;
+use crate::spa::interface::Hook;
+use crate::wrapper::RawWrapper;
+
+#[derive(RawWrapper, Debug)]
+#[repr(transparent)]
+pub struct ModuleEventsRef {
+    #[raw]
+    raw: pw_sys::pw_module_events,
+}
+
+pub type InfoCallback = Box<dyn for<'a> FnMut(&'a ModuleInfoRef)>;
+
+#[derive(Wrapper, Builder)]
+#[builder(setter(skip, strip_option), build_fn(skip), pattern = "owned")]
+pub struct ModuleEvents {
+    #[raw_wrapper]
+    ref_: NonNull<ModuleEventsRef>,
+
+    raw: Pin<Box<ModuleEventsRef>>,
+    hook: Pin<Box<Hook>>,
+
+    #[builder(setter)]
+    info: Option<InfoCallback>,
+}
+
+impl ModuleEvents {
+    unsafe extern "C" fn info_call(data: *mut ::std::os::raw::c_void, info: *const pw_module_info) {
+        if let Some(module_events) = (data as *mut ModuleEvents).as_mut() {
+            if let Some(callback) = &mut module_events.info {
+                callback(ModuleInfoRef::from_raw_ptr(info));
+            }
+        }
+    }
+
+    pub fn hook(&self) -> &Pin<Box<Hook>> {
+        &self.hook
+    }
+
+    pub fn version(&self) -> u32 {
+        self.raw.raw.version
+    }
+}
+
+// todo: channel builder
+
+impl ModuleEventsBuilder {
+    events_builder_build! {
+        ModuleEvents,
+        pw_module_events,
+        info => info_call,
+    }
+}
+
+impl Debug for ModuleEvents {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModuleEvents")
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
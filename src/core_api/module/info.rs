@@ -0,0 +1,103 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::ptr::NonNull;
+
+use bitflags::bitflags;
+
+use pipewire_wrapper_proc_macro::{RawWrapper, Wrapper};
+
+use crate::spa::dict::DictRef;
+use crate::wrapper::RawWrapper;
+
+#[derive(RawWrapper, Debug)]
+#[repr(transparent)]
+pub struct ModuleInfoRef {
+    #[raw]
+    raw: pw_sys::pw_module_info,
+}
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[repr(transparent)]
+    pub struct ChangeMask: u64 {
+        const PROPS = pw_sys::PW_MODULE_CHANGE_MASK_PROPS as u64;
+        const ALL = pw_sys::PW_MODULE_CHANGE_MASK_ALL as u64;
+    }
+}
+
+impl ModuleInfoRef {
+    pub fn id(&self) -> u32 {
+        self.raw.id
+    }
+
+    pub fn name(&self) -> Option<&CStr> {
+        unsafe { self.raw.name.as_ref().map(|ptr| CStr::from_ptr(ptr)) }
+    }
+
+    pub fn filename(&self) -> Option<&CStr> {
+        unsafe { self.raw.filename.as_ref().map(|ptr| CStr::from_ptr(ptr)) }
+    }
+
+    pub fn args(&self) -> Option<&CStr> {
+        unsafe { self.raw.args.as_ref().map(|ptr| CStr::from_ptr(ptr)) }
+    }
+
+    pub fn change_mask(&self) -> ChangeMask {
+        ChangeMask::from_bits_retain(self.raw.change_mask)
+    }
+
+    pub fn props(&self) -> &DictRef {
+        unsafe { DictRef::from_raw_ptr(self.raw.props) }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ModuleInfo {
+    id: u32,
+    name: Option<CString>,
+    filename: Option<CString>,
+    args: Option<CString>,
+    change_mask: ChangeMask,
+    props: HashMap<CString, CString>,
+}
+
+impl ModuleInfo {
+    pub fn from_ref(ref_: &ModuleInfoRef) -> Self {
+        Self {
+            id: ref_.id(),
+            name: ref_.name().map(CString::from),
+            filename: ref_.filename().map(CString::from),
+            args: ref_.args().map(CString::from),
+            change_mask: ref_.change_mask(),
+            props: ref_.props().into(),
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+    pub fn name(&self) -> &Option<CString> {
+        &self.name
+    }
+    pub fn filename(&self) -> &Option<CString> {
+        &self.filename
+    }
+    pub fn args(&self) -> &Option<CString> {
+        &self.args
+    }
+    pub fn change_mask(&self) -> ChangeMask {
+        self.change_mask
+    }
+    pub fn props(&self) -> &HashMap<CString, CString> {
+        &self.props
+    }
+}
+
+impl From<&ModuleInfoRef> for ModuleInfo {
+    fn from(value: &ModuleInfoRef) -> Self {
+        ModuleInfo::from_ref(value)
+    }
+}
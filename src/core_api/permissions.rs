@@ -57,8 +57,30 @@ impl Display for Permissions {
     }
 }
 
+/// Permissions for the object with the given id, same layout as [pw_sys::pw_permission]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(C)]
 pub struct ObjectPermissions {
     pub id: u32,
     pub permissions: Permissions,
 }
+
+impl ObjectPermissions {
+    pub fn new(id: u32, permissions: Permissions) -> Self {
+        Self { id, permissions }
+    }
+}
+
+#[test]
+fn test_object_permissions_layout() {
+    use std::mem::{align_of, size_of};
+
+    assert_eq!(
+        size_of::<ObjectPermissions>(),
+        size_of::<pw_sys::pw_permission>()
+    );
+    assert_eq!(
+        align_of::<ObjectPermissions>(),
+        align_of::<pw_sys::pw_permission>()
+    );
+}
@@ -10,12 +10,17 @@ use std::ptr::null_mut;
 use pipewire_wrapper_proc_macro::{interface, proxy_wrapper, RawWrapper};
 
 use crate::core_api::core::Core;
-use crate::core_api::port::events::PortEvents;
+use crate::core_api::loop_::executor::LoopFuture;
+use crate::core_api::port::events::{PortEvents, PortEventsBuilder};
+use crate::core_api::port::info::PortInfo;
+use crate::core_api::proxy::params::{self, PendingParams};
 use crate::core_api::proxy::{Proxy, ProxyRef};
 use crate::core_api::registry::restricted::RegistryBind;
 use crate::i32_as_void_result;
 use crate::listeners::{AddListener, Listeners, OwnListeners};
 use crate::spa::param::ParamType;
+use crate::spa::pod::object::PodObjectRef;
+use crate::spa::pod::pod_buf::AllocPod;
 use crate::spa::pod::PodRef;
 use crate::spa_interface_call;
 use crate::wrapper::{RawWrapper, Wrapper};
@@ -109,3 +114,81 @@ impl OwnListeners for Port {
         &self.listeners
     }
 }
+
+impl Port {
+    /// Enumerate params and block until all of them are received.
+    /// The main loop is iterated until the core done event, see [Core::roundtrip].
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - param type
+    /// * `start` - index of the first param
+    /// * `num` - maximum number of params, 0 for all
+    /// * `filter` - optional filter pod
+    ///
+    /// Returns the object params.
+    pub fn collect_params(
+        &self,
+        id: ParamType,
+        start: u32,
+        num: u32,
+        filter: Option<&PodRef>,
+    ) -> crate::Result<Vec<AllocPod<PodObjectRef>>> {
+        params::collect(
+            self.proxy(),
+            |param| {
+                self.as_ref()
+                    .add_listener(PortEventsBuilder::default().param(param).build())
+            },
+            |seq| self.enum_params(seq, id, start, num, filter),
+        )
+    }
+
+    /// Enumerate params and call the callback with all the received params
+    /// after the core done event.
+    ///
+    /// Returns [PendingParams] that must be kept alive until the callback is called.
+    pub fn collect_params_then<F>(
+        &self,
+        id: ParamType,
+        start: u32,
+        num: u32,
+        filter: Option<&PodRef>,
+        callback: F,
+    ) -> crate::Result<PendingParams<PortEvents>>
+    where
+        F: FnOnce(Vec<AllocPod<PodObjectRef>>) + 'static,
+    {
+        params::collect_then(
+            self.proxy(),
+            |param| {
+                self.as_ref()
+                    .add_listener(PortEventsBuilder::default().param(param).build())
+            },
+            |seq| self.enum_params(seq, id, start, num, filter),
+            callback,
+        )
+    }
+
+    /// Future that is resolved with the next info event.
+    /// The info is emitted after the port is bound and when the port is changed.
+    pub fn info_future(&self) -> LoopFuture<PortInfo> {
+        params::info_future(|info| {
+            self.as_ref()
+                .add_listener(PortEventsBuilder::default().info(info).build())
+        })
+    }
+
+    /// Future that is resolved with the params, see [Port::collect_params_then].
+    pub fn params_future(
+        &self,
+        id: ParamType,
+        start: u32,
+        num: u32,
+        filter: Option<&PodRef>,
+    ) -> crate::Result<LoopFuture<Vec<AllocPod<PodObjectRef>>>> {
+        params::collect_future(|callback| {
+            self.collect_params_then(id, start, num, filter, callback)
+        })
+    }
+}
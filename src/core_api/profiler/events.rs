@@ -0,0 +1,106 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+use std::fmt::{Debug, Formatter};
+use std::pin::Pin;
+use std::ptr::NonNull;
+
+use derive_builder::Builder;
+use pw_sys::pw_profiler_events;
+use spa_sys::spa_pod;
+
+use pipewire_wrapper_proc_macro::{RawWrapper, Wrapper};
+
+use crate::core_api::profiler::info::ProfilerSample;
+use crate::spa::interface::Hook;
+use crate::spa::pod::PodRef;
+use crate::wrapper::RawWrapper;
+
+#[derive(RawWrapper, Debug)]
+#[repr(transparent)]
+pub struct ProfilerEventsRef {
+    #[raw]
+    raw: pw_sys::pw_profiler_events,
+}
+
+/// Parsed profiler samples callback.
+/// The callback is not called when the profile pod cannot be parsed,
+/// [ProfilerEventsBuilder::profile_pod] can be used to get the raw pod.
+pub type ProfileCallback = Box<dyn for<'a> FnMut(&'a [ProfilerSample])>;
+/// Raw profile pod callback.
+pub type ProfilePodCallback = Box<dyn for<'a> FnMut(&'a PodRef)>;
+
+#[derive(Wrapper, Builder)]
+#[builder(setter(skip, strip_option), build_fn(skip), pattern = "owned")]
+pub struct ProfilerEvents {
+    #[raw_wrapper]
+    ref_: NonNull<ProfilerEventsRef>,
+
+    raw: Pin<Box<ProfilerEventsRef>>,
+    hook: Pin<Box<Hook>>,
+
+    #[builder(setter)]
+    profile: Option<ProfileCallback>,
+    #[builder(setter)]
+    profile_pod: Option<ProfilePodCallback>,
+}
+
+impl ProfilerEvents {
+    unsafe extern "C" fn profile_call(data: *mut ::std::os::raw::c_void, pod: *const spa_pod) {
+        if let Some(events) = (data as *mut ProfilerEvents).as_mut() {
+            let pod = PodRef::from_raw_ptr(pod);
+            if let Some(callback) = &mut events.profile_pod {
+                callback(pod);
+            }
+            if let Some(callback) = &mut events.profile {
+                if let Ok(samples) = ProfilerSample::parse_profile(pod) {
+                    callback(&samples);
+                }
+            }
+        }
+    }
+
+    pub fn hook(&self) -> &Pin<Box<Hook>> {
+        &self.hook
+    }
+
+    pub fn version(&self) -> u32 {
+        self.raw.raw.version
+    }
+}
+
+// todo: channel builder
+
+impl ProfilerEventsBuilder {
+    pub fn build(self) -> Pin<Box<ProfilerEvents>> {
+        let hook = Hook::new();
+        let raw = ProfilerEventsRef::from_raw(pw_profiler_events {
+            version: 0,
+            profile: None,
+        });
+        let mut pinned_raw = Box::into_pin(Box::new(raw));
+
+        let mut events = Box::into_pin(Box::new(ProfilerEvents {
+            ref_: NonNull::new(pinned_raw.as_ptr()).unwrap(),
+            raw: pinned_raw,
+            hook,
+            profile: self.profile.flatten(),
+            profile_pod: self.profile_pod.flatten(),
+        }));
+
+        // Both callbacks are fed from the single raw profile event
+        if events.profile.is_some() || events.profile_pod.is_some() {
+            events.raw.raw.profile = Some(ProfilerEvents::profile_call);
+        }
+
+        events
+    }
+}
+
+impl Debug for ProfilerEvents {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProfilerEvents")
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
@@ -0,0 +1,347 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+use std::ffi::CString;
+
+use crate::spa::pod::iterator::PodIterator;
+use crate::spa::pod::object::profiler::ProfilerType;
+use crate::spa::pod::object::{ObjectType, PodObjectRef};
+use crate::spa::pod::struct_::PodStructRef;
+use crate::spa::pod::{BasicType, PodError, PodRef, PodValue};
+use crate::spa::type_::{FractionRef, Type};
+
+/// Sequential reader for the [PodStructRef] fields
+struct StructFields<'a> {
+    iter: PodIterator<'a, PodRef>,
+}
+
+impl<'a> StructFields<'a> {
+    fn new(struct_: &'a PodStructRef) -> crate::Result<Self> {
+        Ok(Self {
+            iter: struct_.value()?,
+        })
+    }
+
+    fn next_field(&mut self) -> crate::Result<&'a PodRef> {
+        Ok(self.iter.next().ok_or(PodError::IndexIsOutOfRange)?)
+    }
+
+    fn mismatch(expected: Type, pod: &PodRef) -> crate::Error {
+        PodError::WrongPodTypeToCast(expected, pod.type_()).into()
+    }
+
+    fn int(&mut self) -> crate::Result<i32> {
+        let pod = self.next_field()?;
+        match pod.downcast()? {
+            BasicType::INT(int) => Ok(int.value()?),
+            _ => Err(Self::mismatch(Type::INT, pod)),
+        }
+    }
+
+    fn long(&mut self) -> crate::Result<i64> {
+        let pod = self.next_field()?;
+        match pod.downcast()? {
+            BasicType::LONG(long) => Ok(long.value()?),
+            _ => Err(Self::mismatch(Type::LONG, pod)),
+        }
+    }
+
+    fn float(&mut self) -> crate::Result<f32> {
+        let pod = self.next_field()?;
+        match pod.downcast()? {
+            BasicType::FLOAT(float) => Ok(float.value()?),
+            _ => Err(Self::mismatch(Type::FLOAT, pod)),
+        }
+    }
+
+    fn double(&mut self) -> crate::Result<f64> {
+        let pod = self.next_field()?;
+        match pod.downcast()? {
+            BasicType::DOUBLE(double) => Ok(double.value()?),
+            _ => Err(Self::mismatch(Type::DOUBLE, pod)),
+        }
+    }
+
+    fn string(&mut self) -> crate::Result<CString> {
+        let pod = self.next_field()?;
+        match pod.downcast()? {
+            BasicType::STRING(string) => Ok(CString::from(string.value()?)),
+            _ => Err(Self::mismatch(Type::STRING, pod)),
+        }
+    }
+
+    fn fraction(&mut self) -> crate::Result<FractionRef> {
+        let pod = self.next_field()?;
+        match pod.downcast()? {
+            BasicType::FRACTION(fraction) => Ok(fraction.value()?),
+            _ => Err(Self::mismatch(Type::FRACTION, pod)),
+        }
+    }
+
+    /// Optional trailing int field, added in the newer PipeWire versions.
+    fn optional_int(&mut self) -> crate::Result<Option<i32>> {
+        match self.iter.next() {
+            Some(pod) => match pod.downcast()? {
+                BasicType::INT(int) => Ok(Some(int.value()?)),
+                _ => Ok(None),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+/// Driver loop statistics.
+#[derive(Clone, Debug)]
+pub struct ProfilerInfo {
+    counter: i64,
+    cpu_load_fast: f32,
+    cpu_load_medium: f32,
+    cpu_load_slow: f32,
+    xrun_count: i32,
+}
+
+impl ProfilerInfo {
+    /// Cycle counter
+    pub fn counter(&self) -> i64 {
+        self.counter
+    }
+    pub fn cpu_load_fast(&self) -> f32 {
+        self.cpu_load_fast
+    }
+    pub fn cpu_load_medium(&self) -> f32 {
+        self.cpu_load_medium
+    }
+    pub fn cpu_load_slow(&self) -> f32 {
+        self.cpu_load_slow
+    }
+    /// Total number of xruns
+    pub fn xrun_count(&self) -> i32 {
+        self.xrun_count
+    }
+}
+
+impl TryFrom<&PodStructRef> for ProfilerInfo {
+    type Error = crate::Error;
+
+    fn try_from(value: &PodStructRef) -> Result<Self, Self::Error> {
+        let mut fields = StructFields::new(value)?;
+        Ok(Self {
+            counter: fields.long()?,
+            cpu_load_fast: fields.float()?,
+            cpu_load_medium: fields.float()?,
+            cpu_load_slow: fields.float()?,
+            xrun_count: fields.int()?,
+        })
+    }
+}
+
+/// Driver clock state.
+#[derive(Clone, Debug)]
+pub struct ProfilerClock {
+    flags: i32,
+    id: i32,
+    name: CString,
+    nsec: i64,
+    rate: FractionRef,
+    position: i64,
+    duration: i64,
+    delay: i64,
+    rate_diff: f64,
+    next_nsec: i64,
+}
+
+impl ProfilerClock {
+    pub fn flags(&self) -> i32 {
+        self.flags
+    }
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+    pub fn name(&self) -> &CString {
+        &self.name
+    }
+    /// Time in nanoseconds against monotonic clock
+    pub fn nsec(&self) -> i64 {
+        self.nsec
+    }
+    pub fn rate(&self) -> FractionRef {
+        self.rate
+    }
+    /// Current position in samples
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+    /// Duration of the current cycle in samples
+    pub fn duration(&self) -> i64 {
+        self.duration
+    }
+    pub fn delay(&self) -> i64 {
+        self.delay
+    }
+    pub fn rate_diff(&self) -> f64 {
+        self.rate_diff
+    }
+    /// Estimated next wakeup time in nanoseconds
+    pub fn next_nsec(&self) -> i64 {
+        self.next_nsec
+    }
+}
+
+impl TryFrom<&PodStructRef> for ProfilerClock {
+    type Error = crate::Error;
+
+    fn try_from(value: &PodStructRef) -> Result<Self, Self::Error> {
+        let mut fields = StructFields::new(value)?;
+        Ok(Self {
+            flags: fields.int()?,
+            id: fields.int()?,
+            name: fields.string()?,
+            nsec: fields.long()?,
+            rate: fields.fraction()?,
+            position: fields.long()?,
+            duration: fields.long()?,
+            delay: fields.long()?,
+            rate_diff: fields.double()?,
+            next_nsec: fields.long()?,
+        })
+    }
+}
+
+/// Node processing timestamps for the cycle.
+#[derive(Clone, Debug)]
+pub struct ProfilerBlock {
+    id: i32,
+    name: CString,
+    prev_signal: i64,
+    signal: i64,
+    awake: i64,
+    finish: i64,
+    status: i32,
+    latency: FractionRef,
+    xrun_count: Option<i32>,
+}
+
+impl ProfilerBlock {
+    /// Node id
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+    /// Node name
+    pub fn name(&self) -> &CString {
+        &self.name
+    }
+    /// Signal time of the previous cycle in nanoseconds
+    pub fn prev_signal(&self) -> i64 {
+        self.prev_signal
+    }
+    /// Time when the node was signaled in nanoseconds
+    pub fn signal(&self) -> i64 {
+        self.signal
+    }
+    /// Time when the node started processing in nanoseconds
+    pub fn awake(&self) -> i64 {
+        self.awake
+    }
+    /// Time when the node finished processing in nanoseconds
+    pub fn finish(&self) -> i64 {
+        self.finish
+    }
+    pub fn status(&self) -> i32 {
+        self.status
+    }
+    pub fn latency(&self) -> FractionRef {
+        self.latency
+    }
+    /// Number of node xruns, reported since PipeWire 0.3.77
+    pub fn xrun_count(&self) -> Option<i32> {
+        self.xrun_count
+    }
+}
+
+impl TryFrom<&PodStructRef> for ProfilerBlock {
+    type Error = crate::Error;
+
+    fn try_from(value: &PodStructRef) -> Result<Self, Self::Error> {
+        let mut fields = StructFields::new(value)?;
+        Ok(Self {
+            id: fields.int()?,
+            name: fields.string()?,
+            prev_signal: fields.long()?,
+            signal: fields.long()?,
+            awake: fields.long()?,
+            finish: fields.long()?,
+            status: fields.int()?,
+            latency: fields.fraction()?,
+            xrun_count: fields.optional_int()?,
+        })
+    }
+}
+
+/// Driver node block
+pub type DriverBlock = ProfilerBlock;
+/// Follower node block
+pub type FollowerBlock = ProfilerBlock;
+
+/// Single profiler sample, contains the driver state and the follower nodes timings.
+#[derive(Clone, Debug, Default)]
+pub struct ProfilerSample {
+    info: Option<ProfilerInfo>,
+    clock: Option<ProfilerClock>,
+    driver_block: Option<DriverBlock>,
+    follower_blocks: Vec<FollowerBlock>,
+}
+
+impl ProfilerSample {
+    /// Parse the profile event pod, that is a struct of the [ObjectType::OBJECT_PROFILER] objects.
+    pub fn parse_profile(pod: &PodRef) -> crate::Result<Vec<ProfilerSample>> {
+        match pod.downcast()? {
+            BasicType::STRUCT(struct_) => struct_
+                .value()?
+                .map(|element| match element.downcast()? {
+                    BasicType::OBJECT(object) => ProfilerSample::try_from(object),
+                    _ => Err(PodError::WrongPodTypeToCast(Type::OBJECT, element.type_()).into()),
+                })
+                .collect(),
+            BasicType::OBJECT(object) => Ok(vec![ProfilerSample::try_from(object)?]),
+            _ => Err(PodError::WrongPodTypeToCast(Type::STRUCT, pod.type_()).into()),
+        }
+    }
+
+    pub fn info(&self) -> &Option<ProfilerInfo> {
+        &self.info
+    }
+    pub fn clock(&self) -> &Option<ProfilerClock> {
+        &self.clock
+    }
+    pub fn driver_block(&self) -> &Option<DriverBlock> {
+        &self.driver_block
+    }
+    pub fn follower_blocks(&self) -> &Vec<FollowerBlock> {
+        &self.follower_blocks
+    }
+}
+
+impl TryFrom<&PodObjectRef> for ProfilerSample {
+    type Error = crate::Error;
+
+    fn try_from(value: &PodObjectRef) -> Result<Self, Self::Error> {
+        if let ObjectType::OBJECT_PROFILER(props) = value.value()? {
+            let mut sample = ProfilerSample::default();
+            for prop in props {
+                match prop.value()? {
+                    ProfilerType::INFO(info) => sample.info = Some(info.try_into()?),
+                    ProfilerType::CLOCK(clock) => sample.clock = Some(clock.try_into()?),
+                    ProfilerType::DRIVER_BLOCK(block) => {
+                        sample.driver_block = Some(block.try_into()?)
+                    }
+                    ProfilerType::FOLLOWER_BLOCK(block) => {
+                        sample.follower_blocks.push(block.try_into()?)
+                    }
+                }
+            }
+            Ok(sample)
+        } else {
+            Err(PodError::UnexpectedObjectType(value.body_type().raw).into())
+        }
+    }
+}
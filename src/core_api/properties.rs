@@ -378,6 +378,11 @@ impl Debug for PropertiesRef {
     }
 }
 
+/// Property key from the nul terminated bytes, f.e. [pw_sys::PW_KEY_NODE_NAME].
+pub(crate) fn key(key: &[u8]) -> &CStr {
+    CStr::from_bytes_with_nul(key).unwrap()
+}
+
 #[macro_export]
 macro_rules! properties_new {
     ($($key:expr => $val:expr),+ $(,)?) => {{
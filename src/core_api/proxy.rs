@@ -23,6 +23,7 @@ use crate::spa::SPA_ID_INVALID;
 use crate::wrapper::{RawWrapper, Wrapper};
 
 pub mod events;
+pub mod params;
 
 #[derive(RawWrapper, Debug)]
 #[repr(transparent)]
@@ -0,0 +1,145 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Correlation of the `enum_params` requests with the `param` events.
+//!
+//! The proxies that support params enumeration (Node, Port, Device) reply with the separate
+//! `param` events that have the same seq as the request.
+//! Core `done` event received after the request means that all the params are delivered.
+//! The same proxies provide the futures for the params and for the info events.
+//!
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Mutex;
+
+use crate::core_api::core::events::CoreEvents;
+use crate::core_api::loop_::executor::LoopFuture;
+use crate::core_api::proxy::Proxy;
+use crate::spa::param::ParamType;
+use crate::spa::pod::object::PodObjectRef;
+use crate::spa::pod::pod_buf::AllocPod;
+use crate::spa::pod::{BasicType, PodRef};
+
+static NEXT_SEQ: AtomicI32 = AtomicI32::new(1);
+
+/// Params collected for a single request
+type CollectedParams = Rc<Mutex<Vec<AllocPod<PodObjectRef>>>>;
+
+/// Param events callback, the same for all the proxies with params
+pub(crate) type ParamCallback = Box<dyn for<'a> FnMut(i32, ParamType, u32, u32, &'a PodRef)>;
+
+/// Callback with the collected params
+type ParamsCallback = Box<dyn FnOnce(Vec<AllocPod<PodObjectRef>>)>;
+
+/// Unique seq for the `enum_params` request
+fn next_seq() -> i32 {
+    NEXT_SEQ.fetch_add(1, Ordering::Relaxed) & i32::MAX
+}
+
+/// Param callback that stores the params with the given seq.
+fn collector(seq: i32, params: CollectedParams) -> ParamCallback {
+    Box::new(move |param_seq, _type_, _index, _next, pod| {
+        if param_seq == seq {
+            if let Ok(BasicType::OBJECT(object)) = pod.downcast() {
+                if let Ok(object) = AllocPod::from_pod(object) {
+                    params.lock().unwrap().push(object);
+                }
+            }
+        }
+    })
+}
+
+/// Enumerate params and block until all of them are received.
+///
+/// # Arguments
+///
+/// * `proxy` - proxy that enumerates the params
+/// * `add_listener` - adds the proxy listener with the given param callback
+/// * `enum_params` - sends the `enum_params` request with the given seq
+///
+/// Returns the error if the server reported an error for the proxy before the request finished.
+pub(crate) fn collect<E>(
+    proxy: &Proxy,
+    add_listener: impl FnOnce(ParamCallback) -> Pin<Box<E>>,
+    enum_params: impl FnOnce(i32) -> crate::Result<()>,
+) -> crate::Result<Vec<AllocPod<PodObjectRef>>> {
+    let seq = next_seq();
+    let collected = CollectedParams::default();
+    let _listener = add_listener(collector(seq, Rc::clone(&collected)));
+    enum_params(seq)?;
+
+    let proxy_id = proxy.get_id();
+    let errors = proxy.core().roundtrip()?;
+    if let Some(error) = errors.iter().find(|e| e.id() == proxy_id) {
+        return Err(crate::Error::ErrorCode(-error.res() as u32));
+    }
+    let params = std::mem::take(&mut *collected.lock().unwrap());
+    Ok(params)
+}
+
+/// Enumerate params and call the callback with the params when they are collected,
+/// the arguments are the same as for [collect].
+pub(crate) fn collect_then<E, F>(
+    proxy: &Proxy,
+    add_listener: impl FnOnce(ParamCallback) -> Pin<Box<E>>,
+    enum_params: impl FnOnce(i32) -> crate::Result<()>,
+    callback: F,
+) -> crate::Result<PendingParams<E>>
+where
+    F: FnOnce(Vec<AllocPod<PodObjectRef>>) + 'static,
+{
+    let seq = next_seq();
+    let collected = CollectedParams::default();
+    let param_listener = add_listener(collector(seq, Rc::clone(&collected)));
+    enum_params(seq)?;
+    let done_listener = proxy.core().sync_then(move || {
+        let params = std::mem::take(&mut *collected.lock().unwrap());
+        callback(params);
+    })?;
+    Ok(PendingParams {
+        _param_listener: param_listener,
+        _done_listener: done_listener,
+    })
+}
+
+/// Future that is resolved with the params.
+///
+/// # Arguments
+///
+/// * `collect_params_then` - enumerates the params with the given callback, see [collect_then]
+pub(crate) fn collect_future<E: 'static>(
+    collect_params_then: impl FnOnce(ParamsCallback) -> crate::Result<PendingParams<E>>,
+) -> crate::Result<LoopFuture<Vec<AllocPod<PodObjectRef>>>> {
+    let (sender, future) = LoopFuture::new();
+    let pending = collect_params_then(Box::new(move |params| sender.send(params)))?;
+    Ok(future.keep(pending))
+}
+
+/// Future that is resolved with the next info event.
+///
+/// # Arguments
+///
+/// * `add_listener` - adds the proxy listener with the given info callback
+pub(crate) fn info_future<I, T, E>(
+    add_listener: impl FnOnce(Box<dyn for<'a> FnMut(&'a I)>) -> Pin<Box<E>>,
+) -> LoopFuture<T>
+where
+    I: 'static,
+    T: for<'a> From<&'a I> + 'static,
+    E: 'static,
+{
+    let (sender, future) = LoopFuture::new();
+    let listener = add_listener(Box::new(move |info| sender.send(info.into())));
+    future.keep(listener)
+}
+
+/// Pending params request.
+/// The listeners are unsubscribed on drop, so the callback is not called if the request
+/// is dropped before the params are collected.
+pub struct PendingParams<E> {
+    // Listeners are kept only to be unsubscribed on drop
+    _param_listener: Pin<Box<E>>,
+    _done_listener: Pin<Box<CoreEvents>>,
+}
@@ -4,20 +4,27 @@
 
 //! PipeWire [Registry](https://docs.pipewire.org/group__pw__registry.html) bindings.
 //!
+use std::collections::HashMap;
+use std::ffi::CString;
 use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Mutex;
 
 use pw_sys::pw_proxy;
 
 use pipewire_wrapper_proc_macro::{interface, proxy_wrapper, RawWrapper, Wrapper};
 
 use crate::core_api::core::Core;
+use crate::core_api::loop_::executor::LoopFuture;
+use crate::core_api::permissions::Permissions;
 use crate::core_api::proxy::Proxied;
 use crate::core_api::proxy::{Proxy, ProxyRef};
-use crate::core_api::registry::events::RegistryEvents;
+use crate::core_api::registry::events::{RegistryEvents, RegistryEventsBuilder};
 use crate::core_api::registry::restricted::RegistryBind;
 use crate::core_api::type_info::TypeInfo;
 use crate::listeners::{AddListener, Listeners, OwnListeners};
+use crate::spa::dict::DictRef;
 use crate::spa_interface_call;
 use crate::wrapper::*;
 use crate::{i32_as_void_result, raw_wrapper};
@@ -171,6 +178,78 @@ impl Registry {
         let ref_ = self.bind(id, type_info, version)?;
         Ok(T::from_ref(self.ref_.core().clone(), ref_))
     }
+
+    /// Future that is resolved with the globals announced before the server processed
+    /// the requests sent before this call.
+    /// Called right after the registry is created, it returns all the existing globals.
+    pub fn globals_future(&self) -> crate::Result<LoopFuture<Vec<RegistryGlobal>>> {
+        let globals = Rc::new(Mutex::new(Vec::new()));
+        let listener = self.as_ref().add_listener(
+            RegistryEventsBuilder::default()
+                .global(Box::new({
+                    let globals = globals.clone();
+                    move |id, permissions, type_info, version, props| {
+                        globals.lock().unwrap().push(RegistryGlobal::new(
+                            id,
+                            permissions,
+                            type_info,
+                            version,
+                            props,
+                        ));
+                    }
+                }))
+                .build(),
+        );
+        let (sender, future) = LoopFuture::new();
+        let done_listener = self.ref_.core().sync_then(move || {
+            sender.send(std::mem::take(&mut *globals.lock().unwrap()));
+        })?;
+        Ok(future.keep(listener).keep(done_listener))
+    }
+}
+
+/// Global object announced by the [Registry]
+#[derive(Clone, Debug)]
+pub struct RegistryGlobal {
+    id: u32,
+    permissions: Permissions,
+    type_: CString,
+    version: u32,
+    props: HashMap<CString, CString>,
+}
+
+impl RegistryGlobal {
+    fn new(
+        id: u32,
+        permissions: Permissions,
+        type_info: TypeInfo,
+        version: u32,
+        props: &DictRef,
+    ) -> Self {
+        Self {
+            id,
+            permissions,
+            type_: CString::from(type_info.as_c_str()),
+            version,
+            props: props.into(),
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+    pub fn permissions(&self) -> Permissions {
+        self.permissions
+    }
+    pub fn type_info(&self) -> TypeInfo {
+        TypeInfo::from_c_str(&self.type_)
+    }
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+    pub fn props(&self) -> &HashMap<CString, CString> {
+        &self.props
+    }
 }
 
 pub(crate) mod restricted {
@@ -0,0 +1,211 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+//! PipeWire [Thread Loop](https://docs.pipewire.org/group__pw__thread__loop.html) bindings.
+//!
+use std::ffi::CStr;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ptr::{null, NonNull};
+use std::sync::Arc;
+
+use pipewire_wrapper_proc_macro::{RawWrapper, Wrapper};
+
+use crate::core_api::loop_::restricted::AsLoopRef;
+use crate::core_api::loop_::LoopRef;
+use crate::core_api::properties::Properties;
+use crate::core_api::PipeWire;
+use crate::spa::dict::DictRef;
+use crate::wrapper::{RawWrapper, Wrapper};
+use crate::{i32_as_void_result, new_instance_raw_wrapper};
+
+/// Wrapper for the external [pw_sys::pw_thread_loop] value.
+/// The thread loop runs the [LoopRef] in a separate thread.
+/// The loop lock must be held to access the loop objects from the other threads,
+/// the loop callbacks are always called with the lock held.
+#[derive(RawWrapper, Debug)]
+#[repr(transparent)]
+pub struct ThreadLoopRef {
+    #[raw]
+    raw: pw_sys::pw_thread_loop,
+}
+
+/// Owned wrapper for the [ThreadLoopRef], can be shared between threads.
+#[derive(Debug, Clone, Default)]
+pub struct ThreadLoop {
+    inner: Arc<ThreadLoopInner>,
+}
+
+#[derive(Wrapper, Debug)]
+pub struct ThreadLoopInner {
+    #[raw_wrapper]
+    ref_: NonNull<ThreadLoopRef>,
+
+    pipewire: PipeWire,
+}
+
+// The thread loop methods are thread safe, the loop objects access is guarded by the loop lock
+unsafe impl Send for ThreadLoopInner {}
+unsafe impl Sync for ThreadLoopInner {}
+
+/// Thread loop lock guard, the lock is released on drop.
+/// Must be dropped in the same thread where it was acquired.
+#[derive(Debug)]
+pub struct ThreadLoopLockGuard<'a> {
+    thread_loop: &'a ThreadLoopRef,
+    phantom: PhantomData<*const ()>,
+}
+
+impl ThreadLoopRef {
+    pub fn get_loop(&self) -> &LoopRef {
+        unsafe { LoopRef::from_raw_ptr(pw_sys::pw_thread_loop_get_loop(self.as_raw_ptr())) }
+    }
+
+    /// Start the thread to handle the loop.
+    pub fn start(&self) -> crate::Result<()> {
+        let result = unsafe { pw_sys::pw_thread_loop_start(self.as_raw_ptr()) };
+        i32_as_void_result(result)
+    }
+
+    /// Quit the loop and stop its thread.
+    /// Should not be called from the loop thread.
+    pub fn stop(&self) {
+        unsafe { pw_sys::pw_thread_loop_stop(self.as_raw_ptr()) }
+    }
+
+    /// Lock the loop, the lock is recursive.
+    /// Prefer [ThreadLoopRef::lock_guard] that unlocks automatically.
+    pub fn lock(&self) {
+        unsafe { pw_sys::pw_thread_loop_lock(self.as_raw_ptr()) }
+    }
+
+    /// Unlock the loop, must be called once for each [ThreadLoopRef::lock] call.
+    pub fn unlock(&self) {
+        unsafe { pw_sys::pw_thread_loop_unlock(self.as_raw_ptr()) }
+    }
+
+    /// Lock the loop and return the guard that unlocks it on drop.
+    pub fn lock_guard(&self) -> ThreadLoopLockGuard {
+        self.lock();
+        ThreadLoopLockGuard {
+            thread_loop: self,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Release the lock and wait until [ThreadLoopRef::signal] is called.
+    /// The lock must be held.
+    pub fn wait(&self) {
+        unsafe { pw_sys::pw_thread_loop_wait(self.as_raw_ptr()) }
+    }
+
+    /// Release the lock and wait until [ThreadLoopRef::signal] is called or the timeout is expired.
+    /// The lock must be held.
+    ///
+    /// # Arguments
+    ///
+    /// * `wait_max_sec` - timeout in seconds
+    ///
+    /// Returns false if the timeout is expired.
+    pub fn timed_wait(&self, wait_max_sec: i32) -> crate::Result<bool> {
+        let result = unsafe { pw_sys::pw_thread_loop_timed_wait(self.as_raw_ptr(), wait_max_sec) };
+        // Positive result is ETIMEDOUT
+        i32_as_void_result(result).map(|_| result == 0)
+    }
+
+    /// Signal all the threads waiting with [ThreadLoopRef::wait].
+    ///
+    /// # Arguments
+    ///
+    /// * `wait_for_accept` - block until [ThreadLoopRef::accept] is called
+    pub fn signal(&self, wait_for_accept: bool) {
+        unsafe { pw_sys::pw_thread_loop_signal(self.as_raw_ptr(), wait_for_accept) }
+    }
+
+    /// Signal the loop thread waiting in [ThreadLoopRef::signal] with `wait_for_accept`.
+    pub fn accept(&self) {
+        unsafe { pw_sys::pw_thread_loop_accept(self.as_raw_ptr()) }
+    }
+
+    /// Whether the current thread is the loop thread.
+    pub fn in_thread(&self) -> bool {
+        unsafe { pw_sys::pw_thread_loop_in_thread(self.as_raw_ptr()) }
+    }
+}
+
+impl<'a> ThreadLoopLockGuard<'a> {
+    /// Release the lock and wait until [ThreadLoopRef::signal] is called.
+    pub fn wait(&self) {
+        self.thread_loop.wait()
+    }
+
+    /// Release the lock and wait until [ThreadLoopRef::signal] is called or the timeout is expired.
+    /// Returns false if the timeout is expired.
+    pub fn timed_wait(&self, wait_max_sec: i32) -> crate::Result<bool> {
+        self.thread_loop.timed_wait(wait_max_sec)
+    }
+
+    /// See [ThreadLoopRef::accept]
+    pub fn accept(&self) {
+        self.thread_loop.accept()
+    }
+}
+
+impl Drop for ThreadLoopLockGuard<'_> {
+    fn drop(&mut self) {
+        self.thread_loop.unlock()
+    }
+}
+
+impl Drop for ThreadLoopInner {
+    fn drop(&mut self) {
+        unsafe { pw_sys::pw_thread_loop_destroy(self.as_raw_ptr()) }
+    }
+}
+
+impl AsLoopRef for ThreadLoop {
+    fn loop_(&self) -> &LoopRef {
+        self.get_loop()
+    }
+}
+
+impl ThreadLoopInner {
+    fn new(pipewire: PipeWire, name: Option<&CStr>, props: &DictRef) -> crate::Result<Self> {
+        let name = name.map_or(null(), CStr::as_ptr);
+        let ptr = unsafe { pw_sys::pw_thread_loop_new(name, props.as_raw_ptr()) };
+        let ref_ = new_instance_raw_wrapper(ptr)?;
+        Ok(Self { ref_, pipewire })
+    }
+}
+
+impl Default for ThreadLoopInner {
+    fn default() -> Self {
+        ThreadLoopInner::new(PipeWire::default(), None, Properties::default().dict()).unwrap()
+    }
+}
+
+impl ThreadLoop {
+    /// Create a new thread loop, the loop thread is not started.
+    ///
+    /// # Arguments
+    ///
+    /// * `pipewire` - PipeWire instance
+    /// * `name` - name of the loop thread
+    /// * `props` - loop properties
+    pub fn new(pipewire: PipeWire, name: Option<&CStr>, props: &DictRef) -> crate::Result<Self> {
+        let inner = ThreadLoopInner::new(pipewire, name, props)?;
+        Ok(Self {
+            inner: Arc::new(inner),
+        })
+    }
+}
+
+impl Deref for ThreadLoop {
+    type Target = ThreadLoopInner;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
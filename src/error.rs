@@ -30,6 +30,8 @@ pub enum Error {
     NullPointer,
     /// Pod parse/write error
     PodParseError(PodError),
+    /// Running PipeWire library is older than required, contains the required and the actual versions
+    LibraryVersionTooLow(String, String),
 }
 
 impl Debug for Error {
@@ -57,6 +59,11 @@ impl Display for Error {
             Error::CannotCreateInstance => write!(f, "CannotCreateInstance"),
             Error::NullPointer => write!(f, "NullPointer"),
             Error::PodParseError(pod_error) => write!(f, "PodParseError({:?})", pod_error),
+            Error::LibraryVersionTooLow(required, actual) => write!(
+                f,
+                "PipeWire library version too low, required {}, actual {}",
+                required, actual
+            ),
         }
     }
 }
@@ -37,6 +37,7 @@ pub type AddBufferCallback<T> = Box<dyn for<'a> FnMut(&mut T, &'a BufferRef)>;
 pub type RemoveBufferCallback<T> = Box<dyn for<'a> FnMut(&mut T, &'a BufferRef)>;
 pub type ProcessCallback = Box<dyn for<'a> FnMut(&'a IOPositionRef)>;
 pub type DrainedCallback = Box<dyn FnMut()>;
+/// Command callback, [CommandRef::node_command] can be used to get the typed command value.
 pub type CommandCallback = Box<dyn for<'a> FnMut(&'a CommandRef)>;
 
 #[derive(Wrapper, Builder)]
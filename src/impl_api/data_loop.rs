@@ -87,6 +87,14 @@ impl DataLoopRef {
         unsafe { ThreadRef::from_raw_ptr(pw_sys::pw_data_loop_get_thread(self.as_raw_ptr())) }
     }
 
-    //todo pw_data_loop_invoke
+    /// Call the closure in the data loop thread, see [crate::spa::loop_::LoopRef::invoke].
+    pub fn invoke<F>(&self, block: bool, closure: F) -> crate::Result<i32>
+    where
+        F: FnOnce(bool) -> i32,
+        F: Send + 'static,
+    {
+        self.get_loop().loop_().invoke(block, closure)
+    }
+
     //todo pw_data_loop_set_thread_utils
 }
@@ -14,17 +14,20 @@
 //! # Examples
 //!
 //! ```
+//! use pipewire_wrapper::core_api::loop_::Loop;
 //! use pipewire_wrapper::core_api::main_loop::MainLoop;
 //! use std::time::Duration;
 //! let main_loop = MainLoop::default();
 //!
-//! let callback = |_expirations| {
-//!     main_loop.quit().unwrap();
+//! let callback = {
+//!     let main_loop = main_loop.clone();
+//!     move |_expirations| {
+//!         main_loop.quit().unwrap();
+//!     }
 //! };
-//! let timer = main_loop.get_loop().add_timer(Box::new(callback)).unwrap();
-//! main_loop
-//!     .get_loop()
-//!     .update_timer(&timer, Duration::from_secs(1), Duration::ZERO, false)
+//! let timer = main_loop.add_timer(callback).unwrap();
+//! timer
+//!     .update(Duration::from_secs(1), Duration::ZERO, false)
 //!     .unwrap();
 //! main_loop.run().unwrap();
 //! ```
@@ -105,9 +108,17 @@ fn raw_wrapper<'a, T: RawWrapper>(ptr: *mut T::CType) -> crate::Result<&'a T> {
 enum_wrapper!(TestEnum, u32, VAL1: 12u32, VAL2: 13u32);
 #[cfg(test)]
 enum_wrapper!(TestEnum2, u32, VAL11: 11u32, VAL22: 22u32,);
+#[cfg(test)]
+enum_wrapper!(TestEnum3, u32, _START: 31u32, VAL31: 31u32, _LAST: 32u32);
 #[test]
 fn test_enum_wrapper() {
     assert_eq!(*TestEnum::VAL1.as_raw(), 12u32);
     assert_eq!(format!("{:?}", TestEnum::VAL2), "VAL2");
     assert_eq!(format!("{:?}", TestEnum::from_raw(123u32)), "UNKNOWN(123)");
+    assert_eq!(TestEnum2::VAL22.name(), Some("VAL22"));
+    assert_eq!(TestEnum2::from_raw(123u32).name(), None);
+    assert_eq!(TestEnum2::from_name("VAL11"), Some(TestEnum2::VAL11));
+    assert_eq!(TestEnum2::from_name("VAL1"), None);
+    assert_eq!(format!("{:?}", TestEnum3::VAL31), "_START");
+    assert_eq!(format!("{:?}", TestEnum3::_LAST), "_LAST");
 }
@@ -12,6 +12,8 @@
 /// * [crate::RawWrapper]
 /// * [std::fmt::Debug]
 ///
+/// Constants can be found by name with `from_name()` and `name()` returns the constant name.
+///
 /// # Examples
 /// ```no_run,ignore
 /// enum_wrapper!(
@@ -41,21 +43,33 @@ macro_rules! enum_wrapper {
     };
     (@add_enum_variant $name: ident, $(,)*) => {};
 
-    (@add_debug_variant $self:ident, $f:ident, $enum_variant: ident : $enum_value: path, $($tts:tt)*) => {
+    (@add_name_variant $self:ident, $enum_variant: ident : $enum_value: path, $($tts:tt)*) => {
         if $self.raw == Self::$enum_variant.raw {
-            write!($f, stringify!($enum_variant));
-            return Ok(())
+            return Some(stringify!($enum_variant));
         };
-        enum_wrapper!(@add_debug_variant $self, $f, $($tts)* ,)
+        enum_wrapper!(@add_name_variant $self, $($tts)* ,)
     };
-    (@add_debug_variant $self:ident, $f:ident, $enum_variant: ident : $enum_value: expr, $($tts:tt)*) => {
+    (@add_name_variant $self:ident, $enum_variant: ident : $enum_value: expr, $($tts:tt)*) => {
         if $self.raw == Self::$enum_variant.raw {
-            write!($f, stringify!($enum_variant));
-            return Ok(())
+            return Some(stringify!($enum_variant));
+        };
+        enum_wrapper!(@add_name_variant $self, $($tts)* ,)
+    };
+    (@add_name_variant $self:ident, $(,)*) => {};
+
+    (@add_from_name_variant $name_arg:ident, $enum_variant: ident : $enum_value: path, $($tts:tt)*) => {
+        if $name_arg == stringify!($enum_variant) {
+            return Some(Self::$enum_variant);
+        };
+        enum_wrapper!(@add_from_name_variant $name_arg, $($tts)* ,)
+    };
+    (@add_from_name_variant $name_arg:ident, $enum_variant: ident : $enum_value: expr, $($tts:tt)*) => {
+        if $name_arg == stringify!($enum_variant) {
+            return Some(Self::$enum_variant);
         };
-        enum_wrapper!(@add_debug_variant $self, $f, $($tts)* ,)
+        enum_wrapper!(@add_from_name_variant $name_arg, $($tts)* ,)
     };
-    (@add_debug_variant $self:ident, $f:ident, $(,)*) => {};
+    (@add_from_name_variant $name_arg:ident, $(,)*) => {};
 
     ($name: ident, $repr_type: ty, $($tts:tt)+) => {
         #[derive(pipewire_wrapper_proc_macro::RawWrapper, PartialEq, Eq, Clone, Copy, Hash)]
@@ -67,13 +81,26 @@ macro_rules! enum_wrapper {
 
         impl $name {
             enum_wrapper!(@add_enum_variant $name, $($tts)*);
+
+            /// Name of the first constant with the same value.
+            pub fn name(&self) -> Option<&'static str> {
+                enum_wrapper!(@add_name_variant self, $($tts)*);
+                None
+            }
+
+            /// Constant with the given name.
+            pub fn from_name(name: &str) -> Option<Self> {
+                enum_wrapper!(@add_from_name_variant name, $($tts)*);
+                None
+            }
         }
 
         impl std::fmt::Debug for $name {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                enum_wrapper!(@add_debug_variant self, f, $($tts)*);
-                write!(f, "UNKNOWN({:?})", self.raw);
-                Ok(())
+                match self.name() {
+                    Some(name) => write!(f, "{}", name),
+                    None => write!(f, "UNKNOWN({:?})", self.raw),
+                }
             }
         }
     };
@@ -6,9 +6,14 @@ use std::slice;
 use pipewire_wrapper_proc_macro::RawWrapper;
 
 use crate::enum_wrapper;
+use crate::spa::buffers::audio::{
+    AudioSample, InterleavedBuffer, InterleavedBufferMut, PlanarBuffer, PlanarBufferMut,
+};
 use crate::spa::buffers::data::DataRef;
 use crate::spa::buffers::meta::MetaRef;
+use crate::spa::pod::object::format::AudioFormat;
 
+pub mod audio;
 pub mod chunk;
 pub mod data;
 pub mod meta;
@@ -36,4 +41,42 @@ impl BufferRef {
     pub fn datas_mut(&mut self) -> &mut [DataRef] {
         unsafe { slice::from_raw_parts_mut(self.raw.datas.cast(), self.raw.n_datas as usize) }
     }
+
+    /// Interleaved audio view of the first data.
+    /// Returns [crate::Error::NullPointer] if the buffer has no datas.
+    pub fn interleaved<S: AudioSample>(
+        &self,
+        format: AudioFormat,
+        channels: u32,
+    ) -> crate::Result<InterleavedBuffer<S>> {
+        let data = self.datas().first().ok_or(crate::Error::NullPointer)?;
+        InterleavedBuffer::from_data(data, format, channels)
+    }
+
+    /// Mutable interleaved audio view of the first data.
+    /// Returns [crate::Error::NullPointer] if the buffer has no datas.
+    pub fn interleaved_mut<S: AudioSample>(
+        &mut self,
+        format: AudioFormat,
+        channels: u32,
+    ) -> crate::Result<InterleavedBufferMut<S>> {
+        let data = self
+            .datas_mut()
+            .first_mut()
+            .ok_or(crate::Error::NullPointer)?;
+        InterleavedBufferMut::from_data(data, format, channels)
+    }
+
+    /// Planar audio view of the datas, one data per channel.
+    pub fn planar<S: AudioSample>(&self, format: AudioFormat) -> crate::Result<PlanarBuffer<S>> {
+        PlanarBuffer::from_datas(self.datas(), format)
+    }
+
+    /// Mutable planar audio view of the datas, one data per channel.
+    pub fn planar_mut<S: AudioSample>(
+        &mut self,
+        format: AudioFormat,
+    ) -> crate::Result<PlanarBufferMut<S>> {
+        PlanarBufferMut::from_datas(self.datas_mut(), format)
+    }
 }
@@ -0,0 +1,465 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Typed views of the audio buffer data.
+//!
+//! Interleaved formats keep all the channels in a single [DataRef] as frames,
+//! planar formats keep each channel in a separate [DataRef].
+//! Read-only views cover the valid chunk region, mutable views cover the whole data
+//! and are committed to the chunk with the number of written frames.
+//!
+use std::marker::PhantomData;
+use std::mem::{align_of, size_of};
+use std::slice;
+use std::slice::{ChunksExact, ChunksExactMut};
+
+use crate::spa::buffers::chunk::ChunkRef;
+use crate::spa::buffers::data::DataRef;
+use crate::spa::pod::object::format::AudioFormat;
+
+/// Sample type of the native endian [AudioFormat].
+///
+/// # Safety
+///
+/// Any bit pattern must be a valid value of the type.
+pub unsafe trait AudioSample: Copy + 'static {
+    /// Whether the interleaved format has samples of this type
+    fn is_interleaved_format(format: AudioFormat) -> bool;
+
+    /// Whether the planar format has samples of this type
+    fn is_planar_format(format: AudioFormat) -> bool;
+}
+
+unsafe impl AudioSample for f32 {
+    fn is_interleaved_format(format: AudioFormat) -> bool {
+        format == AudioFormat::F32
+    }
+
+    fn is_planar_format(format: AudioFormat) -> bool {
+        format == AudioFormat::F32P || format == AudioFormat::DSP_F32
+    }
+}
+
+unsafe impl AudioSample for f64 {
+    fn is_interleaved_format(format: AudioFormat) -> bool {
+        format == AudioFormat::F64
+    }
+
+    fn is_planar_format(format: AudioFormat) -> bool {
+        format == AudioFormat::F64P || format == AudioFormat::DSP_F64
+    }
+}
+
+unsafe impl AudioSample for i8 {
+    fn is_interleaved_format(format: AudioFormat) -> bool {
+        format == AudioFormat::S8
+    }
+
+    fn is_planar_format(format: AudioFormat) -> bool {
+        format == AudioFormat::S8P
+    }
+}
+
+unsafe impl AudioSample for u8 {
+    fn is_interleaved_format(format: AudioFormat) -> bool {
+        format == AudioFormat::U8
+    }
+
+    fn is_planar_format(format: AudioFormat) -> bool {
+        format == AudioFormat::U8P
+    }
+}
+
+unsafe impl AudioSample for i16 {
+    fn is_interleaved_format(format: AudioFormat) -> bool {
+        format == AudioFormat::S16
+    }
+
+    fn is_planar_format(format: AudioFormat) -> bool {
+        format == AudioFormat::S16P
+    }
+}
+
+/// S24_32 samples are stored in the lower 24 bits of i32
+unsafe impl AudioSample for i32 {
+    fn is_interleaved_format(format: AudioFormat) -> bool {
+        format == AudioFormat::S32 || format == AudioFormat::S24_32
+    }
+
+    fn is_planar_format(format: AudioFormat) -> bool {
+        format == AudioFormat::S32P
+            || format == AudioFormat::S24_32P
+            || format == AudioFormat::DSP_S32
+    }
+}
+
+/// Read-only view of the interleaved audio data.
+#[derive(Debug)]
+pub struct InterleavedBuffer<'a, S: AudioSample> {
+    samples: &'a [S],
+    channels: usize,
+}
+
+/// Mutable view of the interleaved audio data.
+#[derive(Debug)]
+pub struct InterleavedBufferMut<'a, S: AudioSample> {
+    samples: &'a mut [S],
+    chunk: &'a mut ChunkRef,
+    channels: usize,
+}
+
+/// Read-only view of the planar audio data, one [DataRef] per channel.
+#[derive(Debug)]
+pub struct PlanarBuffer<'a, S: AudioSample> {
+    datas: &'a [DataRef],
+    phantom: PhantomData<S>,
+}
+
+/// Mutable view of the planar audio data, one [DataRef] per channel.
+#[derive(Debug)]
+pub struct PlanarBufferMut<'a, S: AudioSample> {
+    datas: &'a mut [DataRef],
+    phantom: PhantomData<S>,
+}
+
+fn check_format<S: AudioSample>(format: AudioFormat, planar: bool) -> crate::Result<()> {
+    let matches = if planar {
+        S::is_planar_format(format)
+    } else {
+        S::is_interleaved_format(format)
+    };
+    if matches {
+        Ok(())
+    } else {
+        Err(crate::Error::TypeMismatch)
+    }
+}
+
+/// Pointer to the data at the given offset, checks that the data is mapped and aligned.
+fn data_ptr<S: AudioSample>(data: &DataRef, offset: u32) -> crate::Result<*mut S> {
+    let ptr = data.data() as *mut u8;
+    if ptr.is_null() {
+        return Err(crate::Error::NullPointer);
+    }
+    let ptr = unsafe { ptr.add(offset as usize) };
+    if ptr as usize % align_of::<S>() != 0 {
+        return Err(crate::Error::ErrorMessage("Audio data is not aligned"));
+    }
+    Ok(ptr as *mut S)
+}
+
+/// Valid data region (offset, size) of the chunk, limited with the data max size.
+fn chunk_region(data: &DataRef) -> (u32, u32) {
+    let max_size = data.max_size();
+    let chunk = data.chunk();
+    let offset = chunk.offset().min(max_size);
+    let size = chunk.size().min(max_size - offset);
+    (offset, size)
+}
+
+/// Samples of the valid chunk region, empty if the data is not mapped or not aligned.
+fn chunk_samples<S: AudioSample>(data: &DataRef) -> &[S] {
+    let (offset, size) = chunk_region(data);
+    match data_ptr::<S>(data, offset) {
+        Ok(ptr) => unsafe { slice::from_raw_parts(ptr, size as usize / size_of::<S>()) },
+        Err(_) => &[],
+    }
+}
+
+fn check_stride(chunk: &ChunkRef, stride: usize) -> crate::Result<()> {
+    if chunk.stride() == 0 || chunk.stride() as usize == stride {
+        Ok(())
+    } else {
+        Err(crate::Error::ErrorMessage(
+            "Chunk stride does not match the audio format",
+        ))
+    }
+}
+
+impl<'a, S: AudioSample> InterleavedBuffer<'a, S> {
+    /// View of the valid chunk region.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - buffer data
+    /// * `format` - negotiated interleaved format, must match the sample type
+    /// * `channels` - negotiated channels count
+    pub fn from_data(data: &'a DataRef, format: AudioFormat, channels: u32) -> crate::Result<Self> {
+        check_format::<S>(format, false)?;
+        let channels = channels.max(1) as usize;
+        let stride = channels * size_of::<S>();
+        check_stride(data.chunk(), stride)?;
+        let (offset, size) = chunk_region(data);
+        let frames = size as usize / stride;
+        let ptr = data_ptr::<S>(data, offset)?;
+        let samples = unsafe { slice::from_raw_parts(ptr, frames * channels) };
+        Ok(Self { samples, channels })
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Number of frames
+    pub fn frames(&self) -> usize {
+        self.samples.len() / self.channels
+    }
+
+    /// Frame samples, one sample per channel
+    pub fn frame(&self, index: usize) -> Option<&'a [S]> {
+        let start = index.checked_mul(self.channels)?;
+        self.samples.get(start..start.checked_add(self.channels)?)
+    }
+
+    /// Iterator over the frames
+    pub fn iter(&self) -> ChunksExact<'a, S> {
+        self.samples.chunks_exact(self.channels)
+    }
+
+    /// All the samples
+    pub fn samples(&self) -> &'a [S] {
+        self.samples
+    }
+}
+
+impl<'a, S: AudioSample> InterleavedBufferMut<'a, S> {
+    /// View of the whole data, limited with the data max size.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - buffer data
+    /// * `format` - negotiated interleaved format, must match the sample type
+    /// * `channels` - negotiated channels count
+    pub fn from_data(
+        data: &'a mut DataRef,
+        format: AudioFormat,
+        channels: u32,
+    ) -> crate::Result<Self> {
+        check_format::<S>(format, false)?;
+        let channels = channels.max(1) as usize;
+        let frames = data.max_size() as usize / (channels * size_of::<S>());
+        let ptr = data_ptr::<S>(data, 0)?;
+        let samples = unsafe { slice::from_raw_parts_mut(ptr, frames * channels) };
+        Ok(Self {
+            samples,
+            chunk: data.chunk_mut(),
+            channels,
+        })
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Max number of frames
+    pub fn frames(&self) -> usize {
+        self.samples.len() / self.channels
+    }
+
+    /// Frame samples, one sample per channel
+    pub fn frame_mut(&mut self, index: usize) -> Option<&mut [S]> {
+        let start = index.checked_mul(self.channels)?;
+        self.samples
+            .get_mut(start..start.checked_add(self.channels)?)
+    }
+
+    /// Iterator over the frames
+    pub fn iter_mut(&mut self) -> ChunksExactMut<S> {
+        self.samples.chunks_exact_mut(self.channels)
+    }
+
+    /// All the samples
+    pub fn samples_mut(&mut self) -> &mut [S] {
+        self.samples
+    }
+
+    /// Set the chunk offset, stride and size for the written frames.
+    ///
+    /// Returns error if the frames count exceeds the buffer size.
+    pub fn commit(self, frames: usize) -> crate::Result<()> {
+        if frames > self.frames() {
+            return Err(crate::Error::ErrorMessage(
+                "Frames count exceeds the buffer size",
+            ));
+        }
+        let stride = self.channels * size_of::<S>();
+        self.chunk.set_offset(0);
+        self.chunk.set_stride(stride as i32);
+        self.chunk.set_size((frames * stride) as u32);
+        Ok(())
+    }
+}
+
+impl<'a, S: AudioSample> PlanarBuffer<'a, S> {
+    /// View of the valid chunk regions.
+    ///
+    /// # Arguments
+    ///
+    /// * `datas` - buffer datas, one per channel
+    /// * `format` - negotiated planar format, must match the sample type
+    pub fn from_datas(datas: &'a [DataRef], format: AudioFormat) -> crate::Result<Self> {
+        check_format::<S>(format, true)?;
+        for data in datas {
+            check_stride(data.chunk(), size_of::<S>())?;
+            data_ptr::<S>(data, chunk_region(data).0)?;
+        }
+        Ok(Self {
+            datas,
+            phantom: PhantomData,
+        })
+    }
+
+    pub fn channels(&self) -> usize {
+        self.datas.len()
+    }
+
+    /// Number of frames, the shortest channel length
+    pub fn frames(&self) -> usize {
+        self.datas
+            .iter()
+            .map(|data| chunk_region(data).1 as usize / size_of::<S>())
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Channel samples
+    pub fn channel(&self, index: usize) -> Option<&'a [S]> {
+        self.datas.get(index).map(chunk_samples)
+    }
+}
+
+impl<'a, S: AudioSample> PlanarBufferMut<'a, S> {
+    /// View of the whole datas, limited with the datas max size.
+    ///
+    /// # Arguments
+    ///
+    /// * `datas` - buffer datas, one per channel
+    /// * `format` - negotiated planar format, must match the sample type
+    pub fn from_datas(datas: &'a mut [DataRef], format: AudioFormat) -> crate::Result<Self> {
+        check_format::<S>(format, true)?;
+        for data in datas.iter() {
+            data_ptr::<S>(data, 0)?;
+        }
+        Ok(Self {
+            datas,
+            phantom: PhantomData,
+        })
+    }
+
+    pub fn channels(&self) -> usize {
+        self.datas.len()
+    }
+
+    /// Max number of frames, the shortest channel length
+    pub fn frames(&self) -> usize {
+        self.datas
+            .iter()
+            .map(|data| data.max_size() as usize / size_of::<S>())
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Channel samples
+    pub fn channel_mut(&mut self, index: usize) -> Option<&mut [S]> {
+        let data = self.datas.get_mut(index)?;
+        let len = data.max_size() as usize / size_of::<S>();
+        let ptr = data_ptr::<S>(data, 0).ok()?;
+        Some(unsafe { slice::from_raw_parts_mut(ptr, len) })
+    }
+
+    /// Set the chunks offset, stride and size for the written frames.
+    ///
+    /// Returns error if the frames count exceeds the buffer size.
+    pub fn commit(self, frames: usize) -> crate::Result<()> {
+        if frames > self.frames() {
+            return Err(crate::Error::ErrorMessage(
+                "Frames count exceeds the buffer size",
+            ));
+        }
+        for data in self.datas {
+            let chunk = data.chunk_mut();
+            chunk.set_offset(0);
+            chunk.set_stride(size_of::<S>() as i32);
+            chunk.set_size((frames * size_of::<S>()) as u32);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn test_data(samples: &mut [f32], chunk: &mut spa_sys::spa_chunk) -> DataRef {
+    use crate::wrapper::RawWrapper;
+
+    DataRef::from_raw(spa_sys::spa_data {
+        type_: spa_sys::SPA_DATA_MemPtr,
+        flags: spa_sys::SPA_DATA_FLAG_READWRITE,
+        fd: -1,
+        mapoffset: 0,
+        maxsize: (samples.len() * size_of::<f32>()) as u32,
+        data: samples.as_mut_ptr().cast(),
+        chunk,
+    })
+}
+
+#[test]
+fn test_interleaved_buffer() {
+    let mut samples = [0f32; 8];
+    let mut chunk = spa_sys::spa_chunk {
+        offset: 0,
+        size: 0,
+        stride: 0,
+        flags: 0,
+    };
+    let mut data = test_data(&mut samples, &mut chunk);
+
+    assert!(InterleavedBufferMut::<i16>::from_data(&mut data, AudioFormat::F32, 2).is_err());
+    let mut buffer =
+        InterleavedBufferMut::<f32>::from_data(&mut data, AudioFormat::F32, 2).unwrap();
+    assert_eq!(buffer.frames(), 4);
+    for (index, frame) in buffer.iter_mut().take(3).enumerate() {
+        frame.fill(index as f32);
+    }
+    assert!(buffer.frame_mut(usize::MAX / 2 + 1).is_none());
+    assert!(buffer.commit(5).is_err());
+    let buffer = InterleavedBufferMut::<f32>::from_data(&mut data, AudioFormat::F32, 2).unwrap();
+    buffer.commit(3).unwrap();
+
+    let buffer = InterleavedBuffer::<f32>::from_data(&data, AudioFormat::F32, 2).unwrap();
+    assert_eq!(data.chunk().stride(), 8);
+    assert_eq!(data.chunk().size(), 24);
+    assert_eq!(buffer.frames(), 3);
+    assert_eq!(buffer.frame(2), Some(&[2f32, 2f32][..]));
+    assert_eq!(buffer.frame(3), None);
+    assert_eq!(buffer.frame(usize::MAX), None);
+    assert!(InterleavedBuffer::<f32>::from_data(&data, AudioFormat::F32, 4).is_err());
+}
+
+#[test]
+fn test_planar_buffer() {
+    let mut left = [0f32; 4];
+    let mut right = [0f32; 4];
+    let mut left_chunk = spa_sys::spa_chunk {
+        offset: 0,
+        size: 0,
+        stride: 0,
+        flags: 0,
+    };
+    let mut right_chunk = left_chunk;
+    let mut datas = [
+        test_data(&mut left, &mut left_chunk),
+        test_data(&mut right, &mut right_chunk),
+    ];
+
+    assert!(PlanarBufferMut::<f32>::from_datas(&mut datas, AudioFormat::F32).is_err());
+    let mut buffer = PlanarBufferMut::<f32>::from_datas(&mut datas, AudioFormat::F32P).unwrap();
+    assert_eq!(buffer.channels(), 2);
+    assert_eq!(buffer.frames(), 4);
+    buffer.channel_mut(1).unwrap()[..2].fill(1f32);
+    buffer.commit(2).unwrap();
+
+    let buffer = PlanarBuffer::<f32>::from_datas(&datas, AudioFormat::F32P).unwrap();
+    assert_eq!(buffer.frames(), 2);
+    assert_eq!(buffer.channel(0), Some(&[0f32, 0f32][..]));
+    assert_eq!(buffer.channel(1), Some(&[1f32, 1f32][..]));
+}
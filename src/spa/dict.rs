@@ -47,6 +47,13 @@ impl DictRef {
         self.items().iter()
     }
 
+    /// Value for the given key
+    pub fn get(&self, key: &CStr) -> Option<&CStr> {
+        self.iter()
+            .find(|item| item.key() == key)
+            .map(|item| item.value())
+    }
+
     pub(crate) unsafe fn from_items(items: &[DictItemRef], flags: Flags) -> Self {
         Self {
             raw: spa_dict {
@@ -2,15 +2,21 @@
  * SPDX-License-Identifier: MIT
  */
 use std::fmt::{Debug, Formatter};
-use std::os::fd::RawFd;
-use std::ptr::NonNull;
+use std::marker::PhantomPinned;
+use std::os::fd::{AsFd, BorrowedFd, OwnedFd, RawFd};
+use std::pin::Pin;
+use std::ptr::{null_mut, NonNull};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use spa_sys::spa_source;
 
 use pipewire_wrapper_proc_macro::{spa_interface, RawWrapper, Wrapper};
 
 use crate::error::Error;
+use crate::spa::interface::Hook;
 use crate::spa::loop_::restricted::AsLoopRef;
+use crate::spa::loop_::utils::LoopUtilsRef;
 use crate::spa_interface_call;
 use crate::wrapper::{RawWrapper, SpaInterface, Wrapper};
 use crate::{i32_as_result, i32_as_void_result};
@@ -40,44 +46,68 @@ pub struct SourceRef {
     raw: spa_source,
 }
 
+/// IO source, the source is destroyed and the fd is closed on drop.
 #[derive(Wrapper)]
-pub struct IOSource<'l, T: AsLoopRef + 'l> {
+pub struct IOSource<T: AsLoopRef> {
     #[raw_wrapper]
     ref_: NonNull<SourceRef>,
+    utils: NonNull<LoopUtilsRef<T>>,
     pub(crate) loop_: T,
-    callback: Box<dyn FnMut(RawFd, u32) + 'l>,
+    callback: Box<dyn FnMut(RawFd, u32)>,
+    fd: OwnedFd,
 }
 
+/// Idle source, the source is destroyed on drop.
 #[derive(Wrapper)]
-pub struct IdleSource<'l, T: AsLoopRef + 'l> {
+pub struct IdleSource<T: AsLoopRef> {
     #[raw_wrapper]
     ref_: NonNull<SourceRef>,
+    utils: NonNull<LoopUtilsRef<T>>,
     pub(crate) loop_: T,
-    callback: Box<dyn FnMut() + 'l>,
+    callback: Box<dyn FnMut()>,
 }
 
+/// Event source, the source is destroyed on drop.
 #[derive(Wrapper)]
-pub struct EventSource<'l, T: AsLoopRef + 'l> {
+pub struct EventSource<T: AsLoopRef> {
     #[raw_wrapper]
     ref_: NonNull<SourceRef>,
+    utils: NonNull<LoopUtilsRef<T>>,
     pub(crate) loop_: T,
-    callback: Box<dyn FnMut(u64) + 'l>,
+    callback: Box<dyn FnMut(u64)>,
 }
 
+/// Timer source, the source is destroyed on drop.
 #[derive(Wrapper)]
-pub struct TimerSource<'l, T: AsLoopRef + 'l> {
+pub struct TimerSource<T: AsLoopRef> {
     #[raw_wrapper]
     ref_: NonNull<SourceRef>,
+    utils: NonNull<LoopUtilsRef<T>>,
     pub(crate) loop_: T,
-    callback: Box<dyn FnMut(u64) + 'l>,
+    callback: Box<dyn FnMut(u64)>,
 }
 
+/// Signal source, the source is destroyed on drop.
 #[derive(Wrapper)]
-pub struct SignalSource<'l, T: AsLoopRef + 'l> {
+pub struct SignalSource<T: AsLoopRef> {
     #[raw_wrapper]
     ref_: NonNull<SourceRef>,
+    utils: NonNull<LoopUtilsRef<T>>,
     pub(crate) loop_: T,
-    callback: Box<dyn FnMut(i32) + 'l>,
+    callback: Box<dyn FnMut(i32)>,
+}
+
+/// Loop hook, called in the loop thread.
+pub type HookCallback = Box<dyn FnMut() + Send>;
+
+/// Loop hooks registered with [LoopControlRef::add_hook].
+/// The hooks are removed on drop.
+pub struct LoopControlHooks {
+    hook: Pin<Box<Hook>>,
+    raw: spa_sys::spa_loop_control_hooks,
+    before: Option<HookCallback>,
+    after: Option<HookCallback>,
+    pinned: PhantomPinned,
 }
 
 pub(crate) mod restricted {
@@ -106,7 +136,76 @@ impl LoopRef {
         i32_as_void_result(result)
     }
 
-    //todo invoke
+    /// Call the closure in the loop thread.
+    /// The closure is called immediately when invoked from the loop thread,
+    /// otherwise it is queued and called by the loop.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - wait until the closure is called and return its result
+    /// * `closure` - closure to call, receives `true` when it is called asynchronously
+    /// from the loop thread
+    ///
+    /// Returns the closure result when it is called immediately or `block` is true,
+    /// otherwise the positive async sequence number of the queued call.
+    /// The queued closure is leaked if the loop is destroyed before the closure is called.
+    ///
+    /// Blocking call never returns when the loop is not running or when the loop lock
+    /// is held by the caller, f.e. [ThreadLoopLockGuard](crate::core_api::thread_loop::ThreadLoopLockGuard).
+    pub fn invoke<F>(&self, block: bool, closure: F) -> crate::Result<i32>
+    where
+        F: FnOnce(bool) -> i32,
+        F: Send + 'static,
+    {
+        struct Invoke<F> {
+            closure: F,
+            called: Arc<AtomicBool>,
+        }
+        unsafe extern "C" fn closure_call<F>(
+            _loop: *mut spa_sys::spa_loop,
+            async_: bool,
+            _seq: u32,
+            data: *const std::os::raw::c_void,
+            _size: usize,
+            _user_data: *mut std::os::raw::c_void,
+        ) -> std::os::raw::c_int
+        where
+            F: FnOnce(bool) -> i32,
+        {
+            // The data is a copy of the pointer to the boxed closure
+            let invoke = Box::from_raw(*(data as *const *mut Invoke<F>));
+            invoke.called.store(true, Ordering::Release);
+            (invoke.closure)(async_)
+        }
+        let called = Arc::new(AtomicBool::new(false));
+        let invoke = Box::into_raw(Box::new(Invoke {
+            closure,
+            called: called.clone(),
+        }));
+        let data = &invoke as *const *mut Invoke<F> as *const _;
+        let func = closure_call::<F>;
+        let result = match spa_interface_call!(
+            self,
+            invoke,
+            Some(func),
+            0,
+            data,
+            std::mem::size_of::<*mut Invoke<F>>(),
+            block,
+            null_mut()
+        ) {
+            Ok(result) => result,
+            Err(error) => {
+                drop(unsafe { Box::from_raw(invoke) });
+                return Err(error);
+            }
+        };
+        // The failed call is not queued when the closure is not called, f.e. the queue is full
+        if result < 0 && !called.load(Ordering::Acquire) {
+            drop(unsafe { Box::from_raw(invoke) });
+        }
+        i32_as_result(result, result)
+    }
 }
 
 impl LoopControlRef {
@@ -114,7 +213,64 @@ impl LoopControlRef {
         spa_interface_call!(self, get_fd)
     }
 
-    //todo pub fn add_hook
+    /// Add the hooks that are called in the loop thread on each iteration.
+    ///
+    /// # Arguments
+    ///
+    /// * `before` - called before the loop waits for the events
+    /// * `after` - called after the loop is woken up, before the events are dispatched
+    ///
+    /// Returns the hooks handle, the hooks are removed when it is dropped.
+    /// The handle must be dropped in the loop thread or when the loop is not running,
+    /// and before the loop is destroyed.
+    pub fn add_hook(
+        &self,
+        before: Option<HookCallback>,
+        after: Option<HookCallback>,
+    ) -> crate::Result<Pin<Box<LoopControlHooks>>> {
+        unsafe extern "C" fn before_call(data: *mut std::os::raw::c_void) {
+            if let Some(hooks) = (data as *mut LoopControlHooks).as_mut() {
+                if let Some(callback) = &mut hooks.before {
+                    callback();
+                }
+            }
+        }
+        unsafe extern "C" fn after_call(data: *mut std::os::raw::c_void) {
+            if let Some(hooks) = (data as *mut LoopControlHooks).as_mut() {
+                if let Some(callback) = &mut hooks.after {
+                    callback();
+                }
+            }
+        }
+        let raw = spa_sys::spa_loop_control_hooks {
+            version: 0,
+            before: if before.is_some() {
+                Some(before_call)
+            } else {
+                None
+            },
+            after: if after.is_some() {
+                Some(after_call)
+            } else {
+                None
+            },
+        };
+        let hooks = Box::into_pin(Box::new(LoopControlHooks {
+            hook: Hook::new(),
+            raw,
+            before,
+            after,
+            pinned: PhantomPinned,
+        }));
+        spa_interface_call!(
+            self,
+            add_hook,
+            hooks.hook.as_raw_ptr(),
+            &hooks.raw,
+            &*hooks as *const _ as *mut _
+        )?;
+        Ok(hooks)
+    }
 
     pub fn enter(&self) -> crate::Result<()> {
         spa_interface_call!(self, enter)
@@ -129,46 +285,62 @@ impl LoopControlRef {
     }
 }
 
-impl<T: AsLoopRef> Drop for IOSource<'_, T> {
+impl<T: AsLoopRef> Drop for IOSource<T> {
     fn drop(&mut self) {
-        self.loop_.loop_().remove_source(self.as_ref());
+        unsafe { self.utils.as_ref() }.destroy_source(self.as_ref());
     }
 }
 
-impl<T: AsLoopRef> Drop for IdleSource<'_, T> {
+impl<T: AsLoopRef> Drop for IdleSource<T> {
     fn drop(&mut self) {
-        self.loop_.loop_().remove_source(self.as_ref());
+        unsafe { self.utils.as_ref() }.destroy_source(self.as_ref());
     }
 }
 
-impl<T: AsLoopRef> Drop for EventSource<'_, T> {
+impl<T: AsLoopRef> Drop for EventSource<T> {
     fn drop(&mut self) {
-        self.loop_.loop_().remove_source(self.as_ref());
+        unsafe { self.utils.as_ref() }.destroy_source(self.as_ref());
     }
 }
 
-impl<T: AsLoopRef> Drop for TimerSource<'_, T> {
+impl<T: AsLoopRef> Drop for TimerSource<T> {
     fn drop(&mut self) {
-        self.loop_.loop_().remove_source(self.as_ref());
+        unsafe { self.utils.as_ref() }.destroy_source(self.as_ref());
     }
 }
 
-impl<T: AsLoopRef> Drop for SignalSource<'_, T> {
+impl<T: AsLoopRef> Drop for SignalSource<T> {
     fn drop(&mut self) {
-        self.loop_.loop_().remove_source(self.as_ref());
+        unsafe { self.utils.as_ref() }.destroy_source(self.as_ref());
+    }
+}
+
+impl Debug for LoopControlHooks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoopControlHooks")
+            .field("hook", &self.hook)
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
+
+impl<T: AsLoopRef> AsFd for IOSource<T> {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
     }
 }
 
-impl<T: AsLoopRef> Debug for IOSource<'_, T> {
+impl<T: AsLoopRef> Debug for IOSource<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("IOSource")
             .field("ref_", &self.ref_)
             .field("loop_", &self.loop_)
+            .field("fd", &self.fd)
             .finish()
     }
 }
 
-impl<T: AsLoopRef> Debug for IdleSource<'_, T> {
+impl<T: AsLoopRef> Debug for IdleSource<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("IdleSource")
             .field("ref_", &self.ref_)
@@ -177,7 +349,7 @@ impl<T: AsLoopRef> Debug for IdleSource<'_, T> {
     }
 }
 
-impl<T: AsLoopRef> Debug for EventSource<'_, T> {
+impl<T: AsLoopRef> Debug for EventSource<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("EventSource")
             .field("ref_", &self.ref_)
@@ -186,7 +358,7 @@ impl<T: AsLoopRef> Debug for EventSource<'_, T> {
     }
 }
 
-impl<T: AsLoopRef> Debug for TimerSource<'_, T> {
+impl<T: AsLoopRef> Debug for TimerSource<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TimerSource")
             .field("ref_", &self.ref_)
@@ -195,7 +367,7 @@ impl<T: AsLoopRef> Debug for TimerSource<'_, T> {
     }
 }
 
-impl<T: AsLoopRef> Debug for SignalSource<'_, T> {
+impl<T: AsLoopRef> Debug for SignalSource<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SignalSource")
             .field("ref_", &self.ref_)
@@ -2,7 +2,7 @@
  * SPDX-License-Identifier: MIT
  */
 use std::marker::PhantomData;
-use std::os::fd::RawFd;
+use std::os::fd::{AsRawFd, OwnedFd, RawFd};
 use std::ptr::{null_mut, NonNull};
 use std::time::Duration;
 
@@ -27,16 +27,17 @@ pub struct LoopUtilsRef<T> {
 }
 
 impl<T: AsLoopRef> LoopUtilsRef<T> {
-    pub fn add_io<'l, F>(
+    /// Add the IO source, the fd is owned by the source and closed when the source is dropped.
+    pub fn add_io<F>(
         &self,
         loop_: T,
-        fd: RawFd,
+        fd: OwnedFd,
         mask: u32,
         callback: Box<F>,
-    ) -> crate::Result<IOSource<'l, T>>
+    ) -> crate::Result<IOSource<T>>
     where
         F: FnMut(RawFd, u32),
-        F: 'l,
+        F: 'static,
     {
         unsafe extern "C" fn callback_call<F>(
             data: *mut std::os::raw::c_void,
@@ -51,12 +52,15 @@ impl<T: AsLoopRef> LoopUtilsRef<T> {
         }
         let data = &*callback as *const F as *mut _;
         let func = callback_call::<F>;
-        let source = spa_interface_call!(self, add_io, fd, mask, false, Some(func), data)?;
+        let source =
+            spa_interface_call!(self, add_io, fd.as_raw_fd(), mask, false, Some(func), data)?;
 
         Ok(IOSource {
             ref_: NonNull::new(source as *mut SourceRef).unwrap(),
+            utils: NonNull::from(self),
             loop_,
             callback,
+            fd,
         })
     }
 
@@ -65,15 +69,15 @@ impl<T: AsLoopRef> LoopUtilsRef<T> {
         i32_as_void_result(result)
     }
 
-    pub fn add_idle<'l, F>(
+    pub fn add_idle<F>(
         &self,
         loop_: T,
         enabled: bool,
         callback: Box<F>,
-    ) -> crate::Result<IdleSource<'l, T>>
+    ) -> crate::Result<IdleSource<T>>
     where
         F: FnMut(),
-        F: 'l,
+        F: 'static,
     {
         unsafe extern "C" fn callback_call<F>(data: *mut std::os::raw::c_void)
         where
@@ -88,6 +92,7 @@ impl<T: AsLoopRef> LoopUtilsRef<T> {
 
         Ok(IdleSource {
             ref_: NonNull::new(source as *mut SourceRef).unwrap(),
+            utils: NonNull::from(self),
             loop_,
             callback,
         })
@@ -98,10 +103,10 @@ impl<T: AsLoopRef> LoopUtilsRef<T> {
         i32_as_void_result(result)
     }
 
-    pub fn add_event<'l, F>(&self, loop_: T, callback: Box<F>) -> crate::Result<EventSource<'l, T>>
+    pub fn add_event<F>(&self, loop_: T, callback: Box<F>) -> crate::Result<EventSource<T>>
     where
         F: FnMut(u64),
-        F: 'l,
+        F: 'static,
     {
         unsafe extern "C" fn callback_call<F>(data: *mut std::os::raw::c_void, count: u64)
         where
@@ -116,6 +121,7 @@ impl<T: AsLoopRef> LoopUtilsRef<T> {
 
         Ok(EventSource {
             ref_: NonNull::new(source as *mut SourceRef).unwrap(),
+            utils: NonNull::from(self),
             loop_,
             callback,
         })
@@ -126,10 +132,17 @@ impl<T: AsLoopRef> LoopUtilsRef<T> {
         i32_as_void_result(result)
     }
 
-    pub fn add_timer<'l, F>(&self, loop_: T, callback: Box<F>) -> crate::Result<TimerSource<'l, T>>
+    /// Signal the event source by its raw reference.
+    /// The source must be created with [LoopUtilsRef::add_event].
+    pub(crate) fn signal_source(&self, source: &SourceRef) -> crate::Result<()> {
+        let result = spa_interface_call!(self, signal_event, source.as_raw_ptr())?;
+        i32_as_void_result(result)
+    }
+
+    pub fn add_timer<F>(&self, loop_: T, callback: Box<F>) -> crate::Result<TimerSource<T>>
     where
         F: FnMut(u64),
-        F: 'l,
+        F: 'static,
     {
         unsafe extern "C" fn callback_call<F>(data: *mut std::os::raw::c_void, count: u64)
         where
@@ -144,6 +157,7 @@ impl<T: AsLoopRef> LoopUtilsRef<T> {
 
         Ok(TimerSource {
             ref_: NonNull::new(source as *mut SourceRef).unwrap(),
+            utils: NonNull::from(self),
             loop_,
             callback,
         })
@@ -181,15 +195,15 @@ impl<T: AsLoopRef> LoopUtilsRef<T> {
         i32_as_void_result(result)
     }
 
-    pub fn add_signal<'l, F>(
+    pub fn add_signal<F>(
         &self,
         loop_: T,
         signal_number: i32,
         callback: Box<F>,
-    ) -> crate::Result<SignalSource<'l, T>>
+    ) -> crate::Result<SignalSource<T>>
     where
         F: FnMut(i32),
-        F: 'l,
+        F: 'static,
     {
         unsafe extern "C" fn callback_call<F>(
             data: *mut std::os::raw::c_void,
@@ -212,11 +226,14 @@ impl<T: AsLoopRef> LoopUtilsRef<T> {
 
         Ok(SignalSource {
             ref_: NonNull::new(source as *mut SourceRef).unwrap(),
+            utils: NonNull::from(self),
             loop_,
             callback,
         })
     }
 
+    /// Remove the source from the loop and free it.
+    /// Must be called in the loop thread or when the loop is not running.
     pub fn destroy_source(&self, source: &SourceRef) -> crate::Result<()> {
         spa_interface_call!(self, destroy_source, source.as_raw_ptr())?;
         Ok(())
@@ -38,6 +38,7 @@ pub mod bitmap;
 pub mod bytes;
 pub mod choice;
 pub mod control;
+pub mod debug;
 pub mod id;
 pub mod iterator;
 pub mod object;
@@ -46,8 +47,12 @@ pub mod sequence;
 pub mod string;
 pub mod struct_;
 
+mod names;
 pub mod pod_buf;
 mod restricted;
+#[cfg(feature = "serde")]
+mod serialize;
+mod validate;
 
 macro_rules! primitive_type_pod_impl {
     ($pod_ref_type:ty, $pod_type:expr, $value_raw_type:ty) => {
@@ -168,6 +173,8 @@ pub enum PodError {
     UnexpectedChoiceType(ChoiceType, ChoiceType),
     IOError(std::io::Error),
     PodIsNotAligned,
+    UnknownName(String),
+    PodIsTooDeep,
 }
 
 impl From<PodError> for crate::Error {
@@ -229,6 +236,12 @@ impl Display for PodError {
             PodError::PodIsNotAligned => {
                 write!(f, "Pod is not aligned!")
             }
+            PodError::UnknownName(name) => {
+                write!(f, "Unknown name {}", name)
+            }
+            PodError::PodIsTooDeep => {
+                write!(f, "Pod nesting is too deep")
+            }
         }
     }
 }
@@ -0,0 +1,319 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Human-readable pod representation, the same as `spa_debug_pod` prints.
+//!
+//! ```text
+//! Object: size 136, type Spa:Pod:Object:Param:Format (262147), id Spa:Enum:ParamId:EnumFormat (3)
+//!   Prop: key Spa:Pod:Object:Param:Format:mediaType (1), flags 00000000
+//!     Id 1        (Spa:Enum:MediaType:audio)
+//!   Prop: key Spa:Pod:Object:Param:Format:Audio:rate (65539), flags 00000000
+//!     Choice: type Spa:Enum:Choice:Range, flags 00000000 28 4
+//!       Int 44100
+//!       Int 8000
+//!       Int 96000
+//! ```
+//!
+use std::fmt::{Arguments, Display, Formatter, Write};
+use std::mem::size_of;
+use std::slice;
+
+use crate::spa::pod::choice::ChoiceType;
+use crate::spa::pod::control;
+use crate::spa::pod::names::{
+    choice_type_name, control_type_name, key_name, object_id_name, type_name,
+};
+use crate::spa::pod::object::prop_id_value_name;
+use crate::spa::pod::restricted::{read, read_pod, read_u32};
+use crate::spa::pod::{PodRef, PodResult};
+use crate::spa::type_::Type;
+use crate::wrapper::RawWrapper;
+
+#[cfg(test)]
+use crate::spa::{
+    pod::choice::{range::PodRangeValue, ChoiceStructType},
+    pod::object::audio_enum_format_builder,
+    pod::object::format::{AudioFormat, MediaType},
+    pod::Upcast,
+};
+
+const INDENT: usize = 2;
+
+/// [Display] implementation with the pod tree, can be created by [PodRef::debug].
+pub struct PodDebug<'a> {
+    pod: &'a PodRef,
+}
+
+impl PodRef {
+    /// Human-readable representation comparable with the `spa_debug_pod` output.
+    pub fn debug(&self) -> PodDebug {
+        PodDebug { pod: self }
+    }
+}
+
+impl Display for PodDebug<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let body = unsafe {
+            slice::from_raw_parts(
+                (self.pod.as_raw_ptr() as *const u8).add(size_of::<spa_sys::spa_pod>()),
+                self.pod.size() as usize,
+            )
+        };
+        let mut out = String::new();
+        if let Err(err) = write_value(&mut out, 0, self.pod.type_(), body, &|_| None) {
+            line(&mut out, 0, format_args!("Error: {}", err));
+        }
+        f.write_str(out.trim_end())
+    }
+}
+
+fn line(out: &mut String, indent: usize, args: Arguments) {
+    // Writing to String never fails
+    let _ = writeln!(out, "{:indent$}{}", "", args, indent = indent);
+}
+
+/// Elements of the array or choice body
+fn elements(bytes: &[u8], child_size: usize) -> impl Iterator<Item = &[u8]> {
+    bytes
+        .chunks_exact(child_size.max(1))
+        .take_while(move |_| child_size > 0)
+}
+
+fn write_value(
+    out: &mut String,
+    indent: usize,
+    type_: Type,
+    body: &[u8],
+    id_name: &dyn Fn(u32) -> Option<String>,
+) -> PodResult<()> {
+    match type_ {
+        Type::NONE => line(out, indent, format_args!("None")),
+        Type::BOOL => {
+            let value = i32::from_ne_bytes(read(body, 0)?) != 0;
+            line(out, indent, format_args!("Bool {}", value))
+        }
+        Type::ID => {
+            let value = read_u32(body, 0)?;
+            match id_name(value) {
+                Some(name) => line(out, indent, format_args!("Id {:<8} ({})", value, name)),
+                None => line(out, indent, format_args!("Id {}", value)),
+            }
+        }
+        Type::INT => {
+            let value = i32::from_ne_bytes(read(body, 0)?);
+            line(out, indent, format_args!("Int {}", value))
+        }
+        Type::LONG => {
+            let value = i64::from_ne_bytes(read(body, 0)?);
+            line(out, indent, format_args!("Long {}", value))
+        }
+        Type::FLOAT => {
+            let value = f32::from_ne_bytes(read(body, 0)?);
+            line(out, indent, format_args!("Float {:.6}", value))
+        }
+        Type::DOUBLE => {
+            let value = f64::from_ne_bytes(read(body, 0)?);
+            line(out, indent, format_args!("Double {:.6}", value))
+        }
+        Type::STRING => {
+            let value = body.split(|b| *b == 0).next().unwrap_or_default();
+            let value = String::from_utf8_lossy(value);
+            line(out, indent, format_args!("String \"{}\"", value))
+        }
+        Type::FD => {
+            let value = i64::from_ne_bytes(read(body, 0)?);
+            line(out, indent, format_args!("Fd {}", value))
+        }
+        Type::POINTER => {
+            let pointer_type = Type::from_raw(read_u32(body, 0)?);
+            let value = usize::from_ne_bytes(read(body, 8)?);
+            line(
+                out,
+                indent,
+                format_args!("Pointer {} {:#x}", unknown(type_name(pointer_type)), value),
+            )
+        }
+        Type::RECTANGLE => {
+            let (width, height) = (read_u32(body, 0)?, read_u32(body, 4)?);
+            line(out, indent, format_args!("Rectangle {}x{}", width, height))
+        }
+        Type::FRACTION => {
+            let (num, denom) = (read_u32(body, 0)?, read_u32(body, 4)?);
+            line(out, indent, format_args!("Fraction {}/{}", num, denom))
+        }
+        Type::BITMAP => {
+            line(out, indent, format_args!("Bitmap"));
+            write_bytes(out, indent + INDENT, body)
+        }
+        Type::BYTES => {
+            line(out, indent, format_args!("Bytes"));
+            write_bytes(out, indent + INDENT, body)
+        }
+        Type::ARRAY => {
+            let child_size = read_u32(body, 0)? as usize;
+            let child_type = Type::from_raw(read_u32(body, 4)?);
+            line(
+                out,
+                indent,
+                format_args!(
+                    "Array: child.size {}, child.type {}",
+                    child_size,
+                    unknown(type_name(child_type))
+                ),
+            );
+            let values = &body[size_of::<spa_sys::spa_pod>()..];
+            for value in elements(values, child_size) {
+                write_value(out, indent + INDENT, child_type, value, id_name)?;
+            }
+        }
+        Type::CHOICE => {
+            let choice_type = ChoiceType::from_raw(read_u32(body, 0)?);
+            let flags = read_u32(body, 4)?;
+            let child_size = read_u32(body, 8)? as usize;
+            let child_type = Type::from_raw(read_u32(body, 12)?);
+            line(
+                out,
+                indent,
+                format_args!(
+                    "Choice: type {}, flags {:08x} {} {}",
+                    unknown(choice_type_name(choice_type)),
+                    flags,
+                    body.len(),
+                    child_size
+                ),
+            );
+            let values = &body[size_of::<spa_sys::spa_pod_choice_body>()..];
+            for value in elements(values, child_size) {
+                write_value(out, indent + INDENT, child_type, value, id_name)?;
+            }
+        }
+        Type::STRUCT => {
+            line(out, indent, format_args!("Struct: size {}", body.len()));
+            let mut offset = 0;
+            while offset < body.len() {
+                let (field_type, field, next) = read_pod(body, offset)?;
+                write_value(out, indent + INDENT, field_type, field, id_name)?;
+                offset = next;
+            }
+        }
+        Type::OBJECT => write_object(out, indent, body)?,
+        Type::SEQUENCE => {
+            let unit = read_u32(body, 0)?;
+            line(
+                out,
+                indent,
+                format_args!("Sequence: size {}, unit {}", body.len(), unit),
+            );
+            let mut offset = size_of::<spa_sys::spa_pod_sequence_body>();
+            while offset < body.len() {
+                let control_offset = read_u32(body, offset)?;
+                let control_type = control::Type::from_raw(read_u32(body, offset + 4)?);
+                line(
+                    out,
+                    indent + INDENT,
+                    format_args!(
+                        "Control: offset {}, type {}",
+                        control_offset,
+                        unknown(control_type_name(control_type))
+                    ),
+                );
+                let (value_type, value, next) = read_pod(body, offset + 8)?;
+                write_value(out, indent + INDENT * 2, value_type, value, id_name)?;
+                offset = next;
+            }
+        }
+        type_ => line(
+            out,
+            indent,
+            format_args!("unhandled POD type {}", type_.raw),
+        ),
+    }
+    Ok(())
+}
+
+fn write_object(out: &mut String, indent: usize, body: &[u8]) -> PodResult<()> {
+    let object_type = Type::from_raw(read_u32(body, 0)?);
+    let id = read_u32(body, 4)?;
+    line(
+        out,
+        indent,
+        format_args!(
+            "Object: size {}, type {} ({}), id {} ({})",
+            body.len(),
+            unknown(type_name(object_type)),
+            object_type.raw,
+            unknown(object_id_name(object_type, id)),
+            id
+        ),
+    );
+
+    let mut offset = size_of::<spa_sys::spa_pod_object_body>();
+    while offset < body.len() {
+        let key = read_u32(body, offset)?;
+        let flags = read_u32(body, offset + 4)?;
+        line(
+            out,
+            indent + INDENT,
+            format_args!(
+                "Prop: key {} ({}), flags {:08x}",
+                key_name(object_type, key).as_deref().unwrap_or("unknown"),
+                key,
+                flags
+            ),
+        );
+        let (value_type, value, next) = read_pod(body, offset + 8)?;
+        let id_name = |id| prop_id_value_name(object_type, key, id);
+        write_value(out, indent + INDENT * 2, value_type, value, &id_name)?;
+        offset = next;
+    }
+    Ok(())
+}
+
+fn write_bytes(out: &mut String, indent: usize, bytes: &[u8]) {
+    for (i, chunk) in bytes.chunks(16).enumerate() {
+        let mut hex = String::new();
+        for byte in chunk {
+            let _ = write!(hex, " {:02x}", byte);
+        }
+        line(out, indent, format_args!("{:04x}:{}", i * 16, hex));
+    }
+}
+
+fn unknown(name: Option<&str>) -> &str {
+    name.unwrap_or("unknown")
+}
+
+#[test]
+fn test_debug() {
+    let enum_format = audio_enum_format_builder()
+        .audio_format(ChoiceStructType::NONE(AudioFormat::F32_LE))
+        .audio_rate(ChoiceStructType::RANGE(PodRangeValue::new(
+            44100, 8000, 96000,
+        )))
+        .build()
+        .unwrap();
+    let output = enum_format.as_pod().upcast().debug().to_string();
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert!(lines[0].starts_with("Object: size "));
+    assert!(lines[0].contains(", type Spa:Pod:Object:Param:Format ("));
+    assert!(lines[0].ends_with(", id Spa:Enum:ParamId:EnumFormat (3)"));
+    assert!(lines[1].starts_with("  Prop: key Spa:Pod:Object:Param:Format:mediaType ("));
+    assert_eq!(
+        lines[2],
+        format!(
+            "    Id {:<8} (Spa:Enum:MediaType:audio)",
+            MediaType::AUDIO.raw
+        )
+    );
+    assert!(lines
+        .contains(&"  Prop: key Spa:Pod:Object:Param:Format:Audio:format (65537), flags 00000000"));
+    let format = format!(
+        "      Id {:<8} (Spa:Enum:AudioFormat:F32LE)",
+        AudioFormat::F32_LE.raw
+    );
+    assert!(lines.contains(&format.as_str()));
+    assert!(lines.contains(&"      Int 44100"));
+    assert!(lines.contains(&"      Int 96000"));
+}
@@ -10,6 +10,7 @@ use spa_sys::spa_pod;
 
 use pipewire_wrapper_proc_macro::RawWrapper;
 
+use crate::spa::pod::names;
 use crate::spa::pod::pod_buf::{AllocPod, PodBuf};
 use crate::spa::pod::restricted::{PodHeader, PodRawValue, PrimitiveValue};
 use crate::spa::pod::{FromValue, PodResult, PodValue, SizedPod, WritePod, WriteValue, POD_ALIGN};
@@ -36,11 +37,29 @@ where
     fn to_alloc_pod(&self) -> AllocPod<PodIdRef<Self>> {
         PodIdRef::from_value(self).unwrap()
     }
+
+    /// SPA type info name, like `Spa:Enum:MediaType:audio`, [None] for the types without names.
+    fn type_info_name(&self) -> Option<String> {
+        None
+    }
+
+    /// Value with the SPA type info name or with the short name, like `audio`.
+    fn from_type_info_name(_name: &str) -> Option<Self> {
+        None
+    }
 }
 
 impl PodIdType for u32 {}
 
-impl PodIdType for Type {}
+impl PodIdType for Type {
+    fn type_info_name(&self) -> Option<String> {
+        names::type_name(*self).map(str::to_string)
+    }
+
+    fn from_type_info_name(name: &str) -> Option<Self> {
+        names::type_from_name(name)
+    }
+}
 
 impl<T: PodIdType> PodRawValue for PodIdRef<T>
 where
@@ -0,0 +1,284 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+//! SPA type info names, like `Spa:Pod:Object:Param:Format` or `Spa:Enum:MediaType:audio`.
+//!
+//! The only names table, used by the [debug](super::debug) output and the serde layout.
+//!
+use crate::spa::param::ParamType;
+use crate::spa::pod::choice::ChoiceType;
+use crate::spa::pod::control;
+use crate::spa::pod::object::prop_key_names;
+use crate::spa::type_::Type;
+
+pub(crate) const MEDIA_TYPE: &str = "Spa:Enum:MediaType:";
+pub(crate) const MEDIA_SUBTYPE: &str = "Spa:Enum:MediaSubtype:";
+pub(crate) const AUDIO_FORMAT: &str = "Spa:Enum:AudioFormat:";
+pub(crate) const AUDIO_CHANNEL: &str = "Spa:Enum:AudioChannel:";
+
+const TYPES: &[(Type, &str)] = &[
+    (Type::NONE, "Spa:None"),
+    (Type::BOOL, "Spa:Bool"),
+    (Type::ID, "Spa:Id"),
+    (Type::INT, "Spa:Int"),
+    (Type::LONG, "Spa:Long"),
+    (Type::FLOAT, "Spa:Float"),
+    (Type::DOUBLE, "Spa:Double"),
+    (Type::STRING, "Spa:String"),
+    (Type::BYTES, "Spa:Bytes"),
+    (Type::RECTANGLE, "Spa:Rectangle"),
+    (Type::FRACTION, "Spa:Fraction"),
+    (Type::BITMAP, "Spa:Bitmap"),
+    (Type::ARRAY, "Spa:Array"),
+    (Type::STRUCT, "Spa:Pod:Struct"),
+    (Type::OBJECT, "Spa:Pod:Object"),
+    (Type::SEQUENCE, "Spa:Pod:Sequence"),
+    (Type::POINTER, "Spa:Pointer"),
+    (Type::FD, "Spa:Fd"),
+    (Type::CHOICE, "Spa:Pod:Choice"),
+    (Type::POD, "Spa:Pod"),
+    (Type::POINTER_BUFFER, "Spa:Pointer:Buffer"),
+    (Type::POINTER_META, "Spa:Pointer:Meta"),
+    (Type::POINTER_DICT, "Spa:Pointer:Dict"),
+    (Type::EVENT_DEVICE, "Spa:Pod:Object:Event:Device"),
+    (Type::EVENT_NODE, "Spa:Pod:Object:Event:Node"),
+    (Type::COMMAND_DEVICE, "Spa:Pod:Object:Command:Device"),
+    (Type::COMMAND_NODE, "Spa:Pod:Object:Command:Node"),
+    (Type::OBJECT_PROP_INFO, "Spa:Pod:Object:Param:PropInfo"),
+    (Type::OBJECT_PROPS, "Spa:Pod:Object:Param:Props"),
+    (Type::OBJECT_FORMAT, "Spa:Pod:Object:Param:Format"),
+    (Type::OBJECT_PARAM_BUFFERS, "Spa:Pod:Object:Param:Buffers"),
+    (Type::OBJECT_PARAM_META, "Spa:Pod:Object:Param:Meta"),
+    (Type::OBJECT_PARAM_IO, "Spa:Pod:Object:Param:IO"),
+    (Type::OBJECT_PARAM_PROFILE, "Spa:Pod:Object:Param:Profile"),
+    (
+        Type::OBJECT_PARAM_PORT_CONFIG,
+        "Spa:Pod:Object:Param:PortConfig",
+    ),
+    (Type::OBJECT_PARAM_ROUTE, "Spa:Pod:Object:Param:Route"),
+    (Type::OBJECT_PROFILER, "Spa:Pod:Object:Profiler"),
+    (Type::OBJECT_PARAM_LATENCY, "Spa:Pod:Object:Param:Latency"),
+    (
+        Type::OBJECT_PARAM_PROCESS_LATENCY,
+        "Spa:Pod:Object:Param:ProcessLatency",
+    ),
+];
+
+const PARAM_TYPES: &[(ParamType, &str)] = &[
+    (ParamType::INVALID, "Spa:Enum:ParamId:Invalid"),
+    (ParamType::PROP_INFO, "Spa:Enum:ParamId:PropInfo"),
+    (ParamType::PROPS, "Spa:Enum:ParamId:Props"),
+    (ParamType::ENUM_FORMAT, "Spa:Enum:ParamId:EnumFormat"),
+    (ParamType::FORMAT, "Spa:Enum:ParamId:Format"),
+    (ParamType::BUFFERS, "Spa:Enum:ParamId:Buffers"),
+    (ParamType::META, "Spa:Enum:ParamId:Meta"),
+    (ParamType::IO, "Spa:Enum:ParamId:IO"),
+    (ParamType::ENUM_PROFILE, "Spa:Enum:ParamId:EnumProfile"),
+    (ParamType::PROFILE, "Spa:Enum:ParamId:Profile"),
+    (
+        ParamType::ENUM_PORT_CONFIG,
+        "Spa:Enum:ParamId:EnumPortConfig",
+    ),
+    (ParamType::PORT_CONFIG, "Spa:Enum:ParamId:PortConfig"),
+    (ParamType::ENUM_ROUTE, "Spa:Enum:ParamId:EnumRoute"),
+    (ParamType::ROUTE, "Spa:Enum:ParamId:Route"),
+    (ParamType::CONTROL, "Spa:Enum:ParamId:Control"),
+    (ParamType::LATENCY, "Spa:Enum:ParamId:Latency"),
+    (
+        ParamType::PROCESS_LATENCY,
+        "Spa:Enum:ParamId:ProcessLatency",
+    ),
+];
+
+const CHOICE_TYPES: &[(ChoiceType, &str)] = &[
+    (ChoiceType::NONE, "Spa:Enum:Choice:None"),
+    (ChoiceType::RANGE, "Spa:Enum:Choice:Range"),
+    (ChoiceType::STEP, "Spa:Enum:Choice:Step"),
+    (ChoiceType::ENUM, "Spa:Enum:Choice:Enum"),
+    (ChoiceType::FLAGS, "Spa:Enum:Choice:Flags"),
+];
+
+const CONTROL_TYPES: &[(control::Type, &str)] = &[
+    (control::Type::INVALID, "Spa:Enum:Control:Invalid"),
+    (control::Type::PROPERTIES, "Spa:Enum:Control:Properties"),
+    (control::Type::MIDI, "Spa:Enum:Control:Midi"),
+    (control::Type::OSC, "Spa:Enum:Control:OSC"),
+];
+
+fn find_name<T: PartialEq>(table: &[(T, &'static str)], value: T) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(v, _)| *v == value)
+        .map(|(_, name)| *name)
+}
+
+fn find_value<T: Copy>(table: &[(T, &str)], name: &str) -> Option<T> {
+    table
+        .iter()
+        .find(|(_, n)| *n == name)
+        .map(|(value, _)| *value)
+}
+
+/// Value with the full name or with the short name, the short names must be unique
+#[cfg(feature = "serde")]
+fn find_value_short<T: Copy>(table: &[(T, &str)], name: &str) -> Option<T> {
+    table
+        .iter()
+        .find(|(_, n)| *n == name || short_name(n) == name)
+        .map(|(value, _)| *value)
+}
+
+/// The last part of the name, like `rate` for `Spa:Pod:Object:Param:Format:Audio:rate`
+#[cfg(feature = "serde")]
+pub(crate) fn short_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+pub(crate) fn type_name(type_: Type) -> Option<&'static str> {
+    find_name(TYPES, type_)
+}
+
+/// Type with the full name, the short names are ambiguous, like `Device`
+pub(crate) fn type_from_name(name: &str) -> Option<Type> {
+    find_value(TYPES, name)
+}
+
+/// Param type name of the param object id, like `Spa:Enum:ParamId:EnumFormat`
+pub(crate) fn object_id_name(object_type: Type, id: u32) -> Option<&'static str> {
+    if object_type.raw > Type::_OBJECT_START.raw && object_type.raw < Type::_OBJECT_LAST.raw {
+        find_name(PARAM_TYPES, ParamType::from_raw(id))
+    } else {
+        None
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn param_type_from_name(name: &str) -> Option<ParamType> {
+    find_value_short(PARAM_TYPES, name)
+}
+
+pub(crate) fn choice_type_name(choice_type: ChoiceType) -> Option<&'static str> {
+    find_name(CHOICE_TYPES, choice_type)
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn choice_type_from_name(name: &str) -> Option<ChoiceType> {
+    find_value_short(CHOICE_TYPES, name)
+}
+
+pub(crate) fn control_type_name(control_type: control::Type) -> Option<&'static str> {
+    find_name(CONTROL_TYPES, control_type)
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn control_type_from_name(name: &str) -> Option<control::Type> {
+    find_value_short(CONTROL_TYPES, name)
+}
+
+/// Property key name with the object type prefix, like `Spa:Pod:Object:Param:Format:Audio:rate`
+pub(crate) fn key_name(object_type: Type, key: u32) -> Option<String> {
+    let type_name = type_name(object_type)?;
+    let (_, name) = prop_key_names(object_type)
+        .iter()
+        .find(|(k, _)| *k == key)?;
+    if object_type == Type::OBJECT_FORMAT {
+        // Media specific format keys are prefixed in SPA, like `Audio:rate`
+        let media = ["audio", "video", "application"]
+            .into_iter()
+            .find(|media| name.starts_with(media) && name.len() > media.len());
+        if let Some(media) = media {
+            let (prefix, key) = name.split_at(media.len());
+            let mut chars = key.chars();
+            let first = chars.next().unwrap_or_default();
+            return Some(format!(
+                "{}:{}{}:{}{}",
+                type_name,
+                prefix[..1].to_uppercase(),
+                &prefix[1..],
+                first.to_lowercase(),
+                chars.as_str()
+            ));
+        }
+    }
+    Some(format!("{}:{}", type_name, name))
+}
+
+/// SPA name of the enum constant, like `Spa:Enum:AudioFormat:F32LE` for [F32_LE](
+/// crate::spa::pod::object::format::AudioFormat::F32_LE).
+/// The range markers like `_START_AUDIO` have no SPA names.
+pub(crate) fn id_name(prefix: &str, const_name: &str) -> Option<String> {
+    if const_name.starts_with('_') {
+        return None;
+    }
+    let name = match prefix {
+        MEDIA_TYPE | MEDIA_SUBTYPE => const_name.to_lowercase(),
+        AUDIO_FORMAT => match endianness_suffix(const_name) {
+            Some(suffix) => format!("{}{}", &const_name[..const_name.len() - 3], suffix),
+            None => const_name.to_string(),
+        },
+        AUDIO_CHANNEL => match const_name {
+            "UNKNOWN" => "UNK".to_string(),
+            "START_AUX" => "AUX0".to_string(),
+            name => name.to_string(),
+        },
+        _ => const_name.to_string(),
+    };
+    Some(format!("{}{}", prefix, name))
+}
+
+/// Enum constant name for the SPA name or the short name, the reverse of [id_name].
+pub(crate) fn id_const_name(prefix: &str, name: &str) -> Option<String> {
+    let name = name.strip_prefix(prefix).unwrap_or(name);
+    if name.contains(':') || name.starts_with('_') {
+        return None;
+    }
+    Some(match prefix {
+        MEDIA_TYPE | MEDIA_SUBTYPE => name.to_uppercase(),
+        AUDIO_FORMAT => match ["LE", "BE", "OE"].into_iter().find(|s| name.ends_with(s)) {
+            Some(suffix) if name.len() > suffix.len() => {
+                format!("{}_{}", &name[..name.len() - suffix.len()], suffix)
+            }
+            _ => name.to_string(),
+        },
+        AUDIO_CHANNEL if name == "UNK" => "UNKNOWN".to_string(),
+        _ => name.to_string(),
+    })
+}
+
+/// `LE`, `BE` or `OE` of the audio format constant, like `F32_LE`
+fn endianness_suffix(const_name: &str) -> Option<&'static str> {
+    ["_LE", "_BE", "_OE"]
+        .into_iter()
+        .find(|suffix| const_name.ends_with(suffix) && const_name.len() > suffix.len())
+        .map(|suffix| &suffix[1..])
+}
+
+#[test]
+fn test_id_names() {
+    for (const_name, name) in [
+        ("F32_LE", "Spa:Enum:AudioFormat:F32LE"),
+        ("S24_32_BE", "Spa:Enum:AudioFormat:S24_32BE"),
+        ("F32P", "Spa:Enum:AudioFormat:F32P"),
+        ("ULAW", "Spa:Enum:AudioFormat:ULAW"),
+    ] {
+        assert_eq!(id_name(AUDIO_FORMAT, const_name).unwrap(), name);
+        assert_eq!(id_const_name(AUDIO_FORMAT, name).unwrap(), const_name);
+        assert_eq!(
+            id_const_name(AUDIO_FORMAT, &name[AUDIO_FORMAT.len()..]).unwrap(),
+            const_name
+        );
+    }
+    assert_eq!(
+        id_name(MEDIA_TYPE, "AUDIO").unwrap(),
+        "Spa:Enum:MediaType:audio"
+    );
+    assert_eq!(id_const_name(MEDIA_SUBTYPE, "raw").unwrap(), "RAW");
+    assert_eq!(
+        id_name(AUDIO_CHANNEL, "UNKNOWN").unwrap(),
+        "Spa:Enum:AudioChannel:UNK"
+    );
+    assert!(id_name(MEDIA_SUBTYPE, "_START_AUDIO").is_none());
+    assert!(id_const_name(AUDIO_FORMAT, "_START_INTERLEAVED").is_none());
+    assert!(id_const_name(MEDIA_TYPE, "Spa:Enum:AudioFormat:F32LE").is_none());
+}
@@ -38,7 +38,14 @@ use crate::wrapper::RawWrapper;
 
 use super::restricted::{write_align_padding, write_header, write_value};
 
+#[cfg(test)]
+use crate::spa::pod::object::enum_format::ObjectEnumFormatBuilder;
+
+pub mod command;
 pub mod enum_format;
+pub mod event;
+mod filter;
+mod fixate;
 pub mod format;
 pub mod param_buffers;
 pub mod param_io;
@@ -263,6 +270,13 @@ impl PodObjectRef {
         )
     }
 
+    /// Object properties iterator with the given key type, the body type is not checked.
+    pub(crate) fn props<'a, T: PodPropKeyType<'a>>(&'a self) -> ObjectPropsIterator<'a, T> {
+        let first_element_ptr = unsafe { (&self.raw.body as *const spa_pod_object_body).offset(1) };
+        let size = self.pod_header().size as usize - size_of::<spa_pod_object_body>();
+        PodIterator::new(first_element_ptr.cast(), size)
+    }
+
     fn parse_raw_body<'a>(
         body_ptr: *const spa_pod_object_body,
         size: usize,
@@ -319,6 +333,67 @@ impl PodObjectRef {
     }
 }
 
+/// Readable property key names for the object body type.
+pub(crate) fn prop_key_names(body_type: Type) -> &'static [(u32, &'static str)] {
+    match body_type {
+        Type::OBJECT_PROP_INFO => ObjectPropInfoType::KEY_NAMES,
+        Type::OBJECT_PROPS => ObjectPropType::KEY_NAMES,
+        // Format keys are the superset of EnumFormat keys
+        Type::OBJECT_FORMAT => ObjectFormatType::KEY_NAMES,
+        Type::OBJECT_PARAM_BUFFERS => ParamBuffersType::KEY_NAMES,
+        Type::OBJECT_PARAM_META => ParamMetaType::KEY_NAMES,
+        Type::OBJECT_PARAM_IO => ParamIOType::KEY_NAMES,
+        Type::OBJECT_PARAM_PROFILE => ParamProfileType::KEY_NAMES,
+        Type::OBJECT_PARAM_PORT_CONFIG => ParamPortConfigType::KEY_NAMES,
+        Type::OBJECT_PARAM_ROUTE => ParamRouteType::KEY_NAMES,
+        Type::OBJECT_PROFILER => ProfilerType::KEY_NAMES,
+        Type::OBJECT_PARAM_LATENCY => ParamLatencyType::KEY_NAMES,
+        Type::OBJECT_PARAM_PROCESS_LATENCY => ParamProcessLatencyType::KEY_NAMES,
+        _ => &[],
+    }
+}
+
+/// SPA type info name of the id value for the object body type and property key.
+pub(crate) fn prop_id_value_name(body_type: Type, key: u32, id: u32) -> Option<String> {
+    match body_type {
+        Type::OBJECT_PROP_INFO => ObjectPropInfoType::id_value_name(key, id),
+        Type::OBJECT_PROPS => ObjectPropType::id_value_name(key, id),
+        Type::OBJECT_FORMAT => ObjectFormatType::id_value_name(key, id),
+        Type::OBJECT_PARAM_BUFFERS => ParamBuffersType::id_value_name(key, id),
+        Type::OBJECT_PARAM_META => ParamMetaType::id_value_name(key, id),
+        Type::OBJECT_PARAM_IO => ParamIOType::id_value_name(key, id),
+        Type::OBJECT_PARAM_PROFILE => ParamProfileType::id_value_name(key, id),
+        Type::OBJECT_PARAM_PORT_CONFIG => ParamPortConfigType::id_value_name(key, id),
+        Type::OBJECT_PARAM_ROUTE => ParamRouteType::id_value_name(key, id),
+        Type::OBJECT_PROFILER => ProfilerType::id_value_name(key, id),
+        Type::OBJECT_PARAM_LATENCY => ParamLatencyType::id_value_name(key, id),
+        Type::OBJECT_PARAM_PROCESS_LATENCY => ParamProcessLatencyType::id_value_name(key, id),
+        _ => None,
+    }
+}
+
+/// Id value for the object body type and property key with the SPA type info name.
+#[cfg(feature = "serde")]
+pub(crate) fn prop_id_value_from_name(body_type: Type, key: u32, name: &str) -> Option<u32> {
+    match body_type {
+        Type::OBJECT_PROP_INFO => ObjectPropInfoType::id_value_from_name(key, name),
+        Type::OBJECT_PROPS => ObjectPropType::id_value_from_name(key, name),
+        Type::OBJECT_FORMAT => ObjectFormatType::id_value_from_name(key, name),
+        Type::OBJECT_PARAM_BUFFERS => ParamBuffersType::id_value_from_name(key, name),
+        Type::OBJECT_PARAM_META => ParamMetaType::id_value_from_name(key, name),
+        Type::OBJECT_PARAM_IO => ParamIOType::id_value_from_name(key, name),
+        Type::OBJECT_PARAM_PROFILE => ParamProfileType::id_value_from_name(key, name),
+        Type::OBJECT_PARAM_PORT_CONFIG => ParamPortConfigType::id_value_from_name(key, name),
+        Type::OBJECT_PARAM_ROUTE => ParamRouteType::id_value_from_name(key, name),
+        Type::OBJECT_PROFILER => ProfilerType::id_value_from_name(key, name),
+        Type::OBJECT_PARAM_LATENCY => ParamLatencyType::id_value_from_name(key, name),
+        Type::OBJECT_PARAM_PROCESS_LATENCY => {
+            ParamProcessLatencyType::id_value_from_name(key, name)
+        }
+        _ => None,
+    }
+}
+
 impl<'a> PodRawValue for &'a PodObjectRef {
     type RawValue = spa_sys::spa_pod_object_body;
 
@@ -371,23 +446,31 @@ impl<'a> WritePod for &'a PodObjectRef {
                 }
             }
         };
-        write_header(
-            buffer,
-            (size_of::<spa_sys::spa_pod_object_body>() + content.len()) as u32,
-            Type::OBJECT,
-        )?;
-        write_value(
-            buffer,
-            &spa_sys::spa_pod_object_body {
-                type_: type_.raw,
-                id: 0,
-            },
-        )?;
-        buffer.write_all(content)?;
-        write_align_padding(buffer)
+        write_object(buffer, type_, 0, content)
     }
 }
 
+/// Write the object pod with the given body type, body id and raw properties content.
+fn write_object<W>(buffer: &mut W, type_: Type, id: u32, content: &[u8]) -> PodResult<()>
+where
+    W: Write + Seek,
+{
+    write_header(
+        buffer,
+        (size_of::<spa_sys::spa_pod_object_body>() + content.len()) as u32,
+        Type::OBJECT,
+    )?;
+    write_value(
+        buffer,
+        &spa_sys::spa_pod_object_body {
+            type_: type_.raw,
+            id,
+        },
+    )?;
+    buffer.write_all(content)?;
+    write_align_padding(buffer)
+}
+
 pub trait PodPropKeyType<'a>
 where
     Self: 'a,
@@ -523,6 +606,15 @@ pub enum ObjectType<'a> {
         Type::OBJECT_PARAM_PROCESS_LATENCY.raw,
 }
 
+/// Raw audio enum format with the media props only, the tests add the props they check.
+#[cfg(test)]
+pub(crate) fn audio_enum_format_builder() -> ObjectEnumFormatBuilder {
+    ObjectEnumFormatBuilder::default()
+        .body_id(ParamType::ENUM_FORMAT.into())
+        .media_type(MediaType::AUDIO)
+        .media_subtype(MediaSubType::RAW)
+}
+
 #[test]
 fn from_value() {
     let mut allocated = PodObjectRef::from_value(&ObjectType::OBJECT_FORMAT(
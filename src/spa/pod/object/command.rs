@@ -0,0 +1,178 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+use std::mem::size_of;
+
+use crate::spa::pod::object::prop::ObjectPropType;
+use crate::spa::pod::object::{write_object, ObjectPropsIterator, PodObjectRef};
+use crate::spa::pod::pod_buf::{AllocPod, PodBuf};
+use crate::spa::pod::restricted::PodHeader;
+use crate::spa::pod::{PodError, PodResult};
+use crate::spa::type_::{CommandRef, NodeCommand, Type};
+use crate::wrapper::RawWrapper;
+
+impl CommandRef {
+    /// Command as [PodObjectRef], body type is the command type and body id is the command id.
+    pub fn as_object(&self) -> &PodObjectRef {
+        unsafe { PodObjectRef::from_raw_ptr(self.as_raw_ptr().cast()) }
+    }
+
+    /// Command type, f.e. [Type::COMMAND_NODE]
+    pub fn type_(&self) -> Type {
+        self.as_object().body_type()
+    }
+
+    /// Command id
+    pub fn id(&self) -> u32 {
+        self.as_object().body_id()
+    }
+
+    /// Parse the command as [NodeCommandValue].
+    pub fn node_command(&self) -> PodResult<NodeCommandValue> {
+        self.try_into()
+    }
+}
+
+impl<'a> TryFrom<&'a PodObjectRef> for &'a CommandRef {
+    type Error = PodError;
+
+    fn try_from(value: &'a PodObjectRef) -> Result<Self, Self::Error> {
+        let type_ = value.body_type();
+        if type_ == Type::COMMAND_NODE || type_ == Type::COMMAND_DEVICE {
+            Ok(unsafe { CommandRef::from_raw_ptr(value.as_raw_ptr().cast()) })
+        } else {
+            Err(PodError::UnexpectedObjectType(type_.raw))
+        }
+    }
+}
+
+/// Typed [Type::COMMAND_NODE] command.
+/// Commands without properties are parsed to the corresponding unit variants,
+/// commands with properties or unknown ids are parsed to [NodeCommandValue::PROPS].
+#[derive(Debug)]
+#[allow(non_camel_case_types)]
+pub enum NodeCommandValue<'a> {
+    /// Suspend a node, this removes all configured formats and closes any devices
+    SUSPEND,
+    /// Pause a node, this makes it stop emitting scheduling events
+    PAUSE,
+    /// Start a node, this makes it start emitting scheduling events
+    START,
+    ENABLE,
+    DISABLE,
+    FLUSH,
+    DRAIN,
+    MARKER,
+    /// Begin a set of parameter enumerations or configuration that require the device to remain opened
+    PARAM_BEGIN,
+    /// End a transaction
+    PARAM_END,
+    /// Sent to a driver when some other node emitted the RequestProcess event
+    REQUEST_PROCESS,
+    /// Command with the properties
+    PROPS(NodeCommand, ObjectPropsIterator<'a, ObjectPropType<'a>>),
+}
+
+impl<'a> NodeCommandValue<'a> {
+    /// Command id
+    pub fn id(&self) -> NodeCommand {
+        match self {
+            NodeCommandValue::SUSPEND => NodeCommand::SUSPEND,
+            NodeCommandValue::PAUSE => NodeCommand::PAUSE,
+            NodeCommandValue::START => NodeCommand::START,
+            NodeCommandValue::ENABLE => NodeCommand::ENABLE,
+            NodeCommandValue::DISABLE => NodeCommand::DISABLE,
+            NodeCommandValue::FLUSH => NodeCommand::FLUSH,
+            NodeCommandValue::DRAIN => NodeCommand::DRAIN,
+            NodeCommandValue::MARKER => NodeCommand::MARKER,
+            NodeCommandValue::PARAM_BEGIN => NodeCommand::PARAM_BEGIN,
+            NodeCommandValue::PARAM_END => NodeCommand::PARAM_END,
+            NodeCommandValue::REQUEST_PROCESS => NodeCommand::REQUEST_PROCESS,
+            NodeCommandValue::PROPS(id, _) => *id,
+        }
+    }
+
+    /// Build the command pod, it can be used as [CommandRef] via `try_into()`.
+    pub fn to_alloc_pod(&self) -> PodResult<AllocPod<PodObjectRef>> {
+        let mut buf = PodBuf::<PodObjectRef>::new();
+        let content = match self {
+            NodeCommandValue::PROPS(_, props) => unsafe { props.as_bytes() },
+            _ => &[],
+        };
+        write_object(&mut buf, Type::COMMAND_NODE, self.id().raw, content)?;
+        Ok(buf.into_pod())
+    }
+}
+
+impl<'a> TryFrom<&'a CommandRef> for NodeCommandValue<'a> {
+    type Error = PodError;
+
+    fn try_from(value: &'a CommandRef) -> Result<Self, Self::Error> {
+        let object = value.as_object();
+        let type_ = object.body_type();
+        if type_ != Type::COMMAND_NODE {
+            return Err(PodError::UnexpectedObjectType(type_.raw));
+        }
+        let id = NodeCommand::from_raw(object.body_id());
+        let has_props =
+            object.pod_header().size as usize > size_of::<spa_sys::spa_pod_object_body>();
+        Ok(match id {
+            _ if has_props => NodeCommandValue::PROPS(id, object.props()),
+            NodeCommand::SUSPEND => NodeCommandValue::SUSPEND,
+            NodeCommand::PAUSE => NodeCommandValue::PAUSE,
+            NodeCommand::START => NodeCommandValue::START,
+            NodeCommand::ENABLE => NodeCommandValue::ENABLE,
+            NodeCommand::DISABLE => NodeCommandValue::DISABLE,
+            NodeCommand::FLUSH => NodeCommandValue::FLUSH,
+            NodeCommand::DRAIN => NodeCommandValue::DRAIN,
+            NodeCommand::MARKER => NodeCommandValue::MARKER,
+            NodeCommand::PARAM_BEGIN => NodeCommandValue::PARAM_BEGIN,
+            NodeCommand::PARAM_END => NodeCommandValue::PARAM_END,
+            NodeCommand::REQUEST_PROCESS => NodeCommandValue::REQUEST_PROCESS,
+            _ => NodeCommandValue::PROPS(id, object.props()),
+        })
+    }
+}
+
+#[test]
+fn test_node_command() {
+    let pod = NodeCommandValue::PAUSE.to_alloc_pod().unwrap();
+    let command: &CommandRef = pod.as_pod().try_into().unwrap();
+
+    assert_eq!(command.type_(), Type::COMMAND_NODE);
+    assert_eq!(command.id(), NodeCommand::PAUSE.raw);
+    assert!(matches!(
+        command.node_command().unwrap(),
+        NodeCommandValue::PAUSE
+    ));
+}
+
+#[test]
+fn test_node_command_with_props() {
+    use crate::spa::pod::iterator::AllocatedPodIterator;
+    use crate::spa::pod::object::PodPropRef;
+    use crate::spa::pod::{FromPrimitiveValue, PodBoolRef, PodValue};
+
+    let mute = PodBoolRef::from_primitive(true).unwrap();
+    let props =
+        AllocatedPodIterator::<PodPropRef<ObjectPropType>>::from_values(&[ObjectPropType::MUTE(
+            mute.as_pod(),
+        )])
+        .unwrap();
+    let pod = NodeCommandValue::PROPS(NodeCommand::START, props.iter())
+        .to_alloc_pod()
+        .unwrap();
+    let command: &CommandRef = pod.as_pod().try_into().unwrap();
+
+    if let NodeCommandValue::PROPS(id, mut props) = command.node_command().unwrap() {
+        assert_eq!(id, NodeCommand::START);
+        if let ObjectPropType::MUTE(mute) = props.next().unwrap().value().unwrap() {
+            assert!(mute.value().unwrap());
+        } else {
+            panic!()
+        }
+        assert!(props.next().is_none());
+    } else {
+        panic!()
+    }
+}
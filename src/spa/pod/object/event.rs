@@ -0,0 +1,122 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+use std::mem::size_of;
+
+use crate::spa::pod::object::prop::ObjectPropType;
+use crate::spa::pod::object::{write_object, ObjectPropsIterator, PodObjectRef};
+use crate::spa::pod::pod_buf::{AllocPod, PodBuf};
+use crate::spa::pod::restricted::PodHeader;
+use crate::spa::pod::{PodError, PodResult};
+use crate::spa::type_::{EventRef, NodeEvent, Type};
+use crate::wrapper::RawWrapper;
+
+impl EventRef {
+    /// Event as [PodObjectRef], body type is the event type and body id is the event id.
+    pub fn as_object(&self) -> &PodObjectRef {
+        unsafe { PodObjectRef::from_raw_ptr(self.as_raw_ptr().cast()) }
+    }
+
+    /// Event type, f.e. [Type::EVENT_NODE]
+    pub fn type_(&self) -> Type {
+        self.as_object().body_type()
+    }
+
+    /// Event id
+    pub fn id(&self) -> u32 {
+        self.as_object().body_id()
+    }
+
+    /// Parse the event as [NodeEventValue].
+    pub fn node_event(&self) -> PodResult<NodeEventValue> {
+        self.try_into()
+    }
+}
+
+impl<'a> TryFrom<&'a PodObjectRef> for &'a EventRef {
+    type Error = PodError;
+
+    fn try_from(value: &'a PodObjectRef) -> Result<Self, Self::Error> {
+        let type_ = value.body_type();
+        if type_ == Type::EVENT_NODE || type_ == Type::EVENT_DEVICE {
+            Ok(unsafe { EventRef::from_raw_ptr(value.as_raw_ptr().cast()) })
+        } else {
+            Err(PodError::UnexpectedObjectType(type_.raw))
+        }
+    }
+}
+
+/// Typed [Type::EVENT_NODE] event.
+/// Events without properties are parsed to the corresponding unit variants,
+/// events with properties or unknown ids are parsed to [NodeEventValue::PROPS].
+#[derive(Debug)]
+#[allow(non_camel_case_types)]
+pub enum NodeEventValue<'a> {
+    ERROR,
+    BUFFERING,
+    REQUEST_REFRESH,
+    /// Ask the driver to start processing the graph
+    REQUEST_PROCESS,
+    /// Event with the properties
+    PROPS(NodeEvent, ObjectPropsIterator<'a, ObjectPropType<'a>>),
+}
+
+impl<'a> NodeEventValue<'a> {
+    /// Event id
+    pub fn id(&self) -> NodeEvent {
+        match self {
+            NodeEventValue::ERROR => NodeEvent::ERROR,
+            NodeEventValue::BUFFERING => NodeEvent::BUFFERING,
+            NodeEventValue::REQUEST_REFRESH => NodeEvent::REQUEST_REFRESH,
+            NodeEventValue::REQUEST_PROCESS => NodeEvent::REQUEST_PROCESS,
+            NodeEventValue::PROPS(id, _) => *id,
+        }
+    }
+
+    /// Build the event pod, it can be used as [EventRef] via `try_into()`.
+    pub fn to_alloc_pod(&self) -> PodResult<AllocPod<PodObjectRef>> {
+        let mut buf = PodBuf::<PodObjectRef>::new();
+        let content = match self {
+            NodeEventValue::PROPS(_, props) => unsafe { props.as_bytes() },
+            _ => &[],
+        };
+        write_object(&mut buf, Type::EVENT_NODE, self.id().raw, content)?;
+        Ok(buf.into_pod())
+    }
+}
+
+impl<'a> TryFrom<&'a EventRef> for NodeEventValue<'a> {
+    type Error = PodError;
+
+    fn try_from(value: &'a EventRef) -> Result<Self, Self::Error> {
+        let object = value.as_object();
+        let type_ = object.body_type();
+        if type_ != Type::EVENT_NODE {
+            return Err(PodError::UnexpectedObjectType(type_.raw));
+        }
+        let id = NodeEvent::from_raw(object.body_id());
+        let has_props =
+            object.pod_header().size as usize > size_of::<spa_sys::spa_pod_object_body>();
+        Ok(match id {
+            _ if has_props => NodeEventValue::PROPS(id, object.props()),
+            NodeEvent::ERROR => NodeEventValue::ERROR,
+            NodeEvent::BUFFERING => NodeEventValue::BUFFERING,
+            NodeEvent::REQUEST_REFRESH => NodeEventValue::REQUEST_REFRESH,
+            NodeEvent::REQUEST_PROCESS => NodeEventValue::REQUEST_PROCESS,
+            _ => NodeEventValue::PROPS(id, object.props()),
+        })
+    }
+}
+
+#[test]
+fn test_node_event() {
+    let pod = NodeEventValue::REQUEST_PROCESS.to_alloc_pod().unwrap();
+    let event: &EventRef = pod.as_pod().try_into().unwrap();
+
+    assert_eq!(event.type_(), Type::EVENT_NODE);
+    assert_eq!(event.id(), NodeEvent::REQUEST_PROCESS.raw);
+    assert!(matches!(
+        event.node_event().unwrap(),
+        NodeEventValue::REQUEST_PROCESS
+    ));
+}
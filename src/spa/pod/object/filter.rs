@@ -0,0 +1,708 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Object pods intersection, the same as `spa_pod_filter` does for the objects.
+//!
+use std::cmp::Ordering;
+use std::io::{Cursor, Seek, Write};
+use std::mem::size_of;
+use std::ops::Rem;
+use std::ptr::addr_of;
+use std::slice;
+
+use crate::spa::pod::choice::{ChoiceType, PodChoiceRef};
+use crate::spa::pod::object::{write_object, PodObjectRef, PodPropFlags};
+use crate::spa::pod::pod_buf::{AllocPod, PodBuf};
+use crate::spa::pod::restricted::{
+    read, read_u32, write_align_padding, write_header, write_value, BasicTypePod, CloneTo,
+    PodHeader,
+};
+use crate::spa::pod::{PodError, PodRef, PodResult, POD_ALIGN};
+use crate::spa::type_::Type;
+use crate::wrapper::RawWrapper;
+
+#[cfg(test)]
+use crate::spa::{
+    param::ParamType,
+    pod::choice::{
+        enum_::PodEnumValue, range::PodRangeValue, step::PodStepValue, ChoiceStructType,
+    },
+    pod::object::enum_format::ObjectEnumFormatType,
+    pod::object::{audio_enum_format_builder, ObjectType},
+    pod::PodIntRef,
+    pod::PodValue,
+};
+
+/// Object property with the raw value.
+pub(super) struct Prop<'a> {
+    pub(super) key: u32,
+    pub(super) flags: PodPropFlags,
+    pub(super) pod: &'a PodRef,
+}
+
+/// Choice values, the plain pod is represented as [ChoiceType::NONE] choice.
+pub(super) struct Values<'a> {
+    pub(super) choice: ChoiceType,
+    pub(super) type_: Type,
+    pub(super) values: Vec<&'a [u8]>,
+}
+
+impl<'a> Values<'a> {
+    pub(super) fn from_pod(pod: &'a PodRef) -> PodResult<Self> {
+        if pod.type_() == Type::CHOICE {
+            let choice: &PodChoiceRef = unsafe { pod.cast_unchecked() };
+            let body = choice.body();
+            let size = body.child().size() as usize;
+            let content_size = (pod.size() as usize)
+                .checked_sub(size_of::<spa_sys::spa_pod_choice_body>())
+                .ok_or(PodError::DataIsTooShort(
+                    size_of::<spa_sys::spa_pod_choice_body>(),
+                    pod.size() as usize,
+                ))?;
+            let content = unsafe { slice::from_raw_parts(body.content_ptr(), content_size) };
+            if size == 0 {
+                return Err(PodError::UnexpectedChoiceElementSize(1, 0));
+            }
+            let values: Vec<&[u8]> = content.chunks_exact(size).collect();
+            if values.is_empty() {
+                return Err(PodError::ChoiceElementMissing);
+            }
+            Ok(Self {
+                choice: body.type_(),
+                type_: body.child().type_(),
+                values,
+            })
+        } else {
+            let content = unsafe {
+                slice::from_raw_parts(
+                    (pod.as_raw_ptr() as *const u8).add(size_of::<spa_sys::spa_pod>()),
+                    pod.size() as usize,
+                )
+            };
+            Ok(Self {
+                choice: ChoiceType::NONE,
+                type_: pod.type_(),
+                values: vec![content],
+            })
+        }
+    }
+
+    pub(super) fn size(&self) -> usize {
+        self.values[0].len()
+    }
+
+    pub(super) fn default(&self) -> &'a [u8] {
+        self.values[0]
+    }
+
+    /// Possible values of the None and Enum choices, the Enum default value is skipped
+    pub(super) fn alternatives(&self) -> &[&'a [u8]] {
+        if self.choice == ChoiceType::ENUM {
+            &self.values[1..]
+        } else {
+            &self.values[..1]
+        }
+    }
+
+    /// Min and max values of the Range and Step choices
+    pub(super) fn bounds(&self) -> PodResult<(&'a [u8], &'a [u8])> {
+        match self.values[..] {
+            [_, min, max, ..] => Ok((min, max)),
+            _ => Err(PodError::ChoiceElementMissing),
+        }
+    }
+
+    /// Step value of the Step choice, None for the other choices
+    pub(super) fn step(&self) -> PodResult<Option<&'a [u8]>> {
+        if self.choice == ChoiceType::STEP {
+            let step = self.values.get(3).ok_or(PodError::ChoiceElementMissing)?;
+            Ok(Some(step))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Whether the value fits the Range or Step choice
+    pub(super) fn contains(&self, value: &[u8]) -> PodResult<bool> {
+        let (min, max) = self.bounds()?;
+        let in_range = matches!(
+            compare(self.type_, value, min),
+            Some(Ordering::Equal | Ordering::Greater)
+        ) && matches!(
+            compare(self.type_, value, max),
+            Some(Ordering::Less | Ordering::Equal)
+        );
+        match self.step()? {
+            Some(step) if in_range => Ok(is_step_of(self.type_, value, step)),
+            _ => Ok(in_range),
+        }
+    }
+}
+
+impl PodObjectRef {
+    /// Intersect the object properties with the other object properties,
+    /// like `spa_pod_filter` does.
+    ///
+    /// The properties with the same key are intersected:
+    /// ranges and steps are clipped, enums are intersected and flags are masked.
+    /// The properties present in only one object are copied as is.
+    ///
+    /// Returns None if the objects are not compatible: the body types are different,
+    /// some property has no common values or the mandatory property is missing in the
+    /// other object.
+    /// Returns error if the objects cannot be parsed or the choices cannot be intersected,
+    /// f.e. Range and Flags choices or Step choices with different steps.
+    pub fn filter(&self, other: &PodObjectRef) -> PodResult<Option<AllocPod<PodObjectRef>>> {
+        if self.body_type() != other.body_type() {
+            return Ok(None);
+        }
+        let props = self.raw_props()?;
+        let other_props = other.raw_props()?;
+
+        let mut content = Cursor::new(Vec::new());
+        for prop in props.iter() {
+            let compatible = match other_props.iter().find(|p| p.key == prop.key) {
+                Some(other_prop) => filter_prop(&mut content, prop, other_prop)?,
+                None => copy_prop(&mut content, prop)?,
+            };
+            if !compatible {
+                return Ok(None);
+            }
+        }
+        for other_prop in other_props.iter() {
+            if !props.iter().any(|p| p.key == other_prop.key)
+                && !copy_prop(&mut content, other_prop)?
+            {
+                return Ok(None);
+            }
+        }
+
+        let mut buf = PodBuf::<PodObjectRef>::new();
+        write_object(
+            &mut buf,
+            self.body_type(),
+            self.body_id(),
+            content.get_ref().as_slice(),
+        )?;
+        Ok(Some(buf.into_pod()))
+    }
+
+    pub(super) fn raw_props(&self) -> PodResult<Vec<Prop>> {
+        let size = (self.pod_header().size as usize)
+            .checked_sub(size_of::<spa_sys::spa_pod_object_body>())
+            .ok_or(PodError::DataIsTooShort(
+                size_of::<spa_sys::spa_pod_object_body>(),
+                self.pod_header().size as usize,
+            ))?;
+        let first_prop_ptr = unsafe { addr_of!(self.raw.body).offset(1) as *const u8 };
+        let mut props = Vec::new();
+        let mut offset = 0;
+        while offset + size_of::<spa_sys::spa_pod_prop>() <= size {
+            let prop = unsafe { &*(first_prop_ptr.add(offset) as *const spa_sys::spa_pod_prop) };
+            let prop_size = size_of::<spa_sys::spa_pod_prop>() + prop.value.size as usize;
+            if offset + prop_size > size {
+                return Err(PodError::DataIsTooShort(prop_size, size - offset));
+            }
+            props.push(Prop {
+                key: prop.key,
+                flags: PodPropFlags::from_bits_retain(prop.flags),
+                pod: unsafe { PodRef::from_raw_ptr(&prop.value) },
+            });
+            offset += prop_size.next_multiple_of(POD_ALIGN);
+        }
+        Ok(props)
+    }
+}
+
+/// Copy the property from one object, fails if the property is mandatory
+fn copy_prop<W>(buffer: &mut W, prop: &Prop) -> PodResult<bool>
+where
+    W: Write + Seek,
+{
+    if prop.flags.contains(PodPropFlags::MANDATORY) {
+        return Ok(false);
+    }
+    write_prop_header(buffer, prop.key, prop.flags)?;
+    prop.pod.clone_to(buffer)?;
+    Ok(true)
+}
+
+fn filter_prop<W>(buffer: &mut W, prop: &Prop, other: &Prop) -> PodResult<bool>
+where
+    W: Write + Seek,
+{
+    let values = Values::from_pod(prop.pod)?;
+    let other_values = Values::from_pod(other.pod)?;
+    if values.type_ != other_values.type_ || values.size() != other_values.size() {
+        return Ok(false);
+    }
+    let type_ = values.type_;
+
+    let (choice, result): (ChoiceType, Vec<Vec<u8>>) = match (values.choice, other_values.choice) {
+        (ChoiceType::NONE | ChoiceType::ENUM, ChoiceType::NONE | ChoiceType::ENUM) => {
+            let common = values.alternatives().iter().filter(|value| {
+                other_values
+                    .alternatives()
+                    .iter()
+                    .any(|other| compare(type_, value, other) == Some(Ordering::Equal))
+            });
+            (
+                ChoiceType::ENUM,
+                common.map(|value| value.to_vec()).collect(),
+            )
+        }
+        (ChoiceType::NONE | ChoiceType::ENUM, ChoiceType::RANGE | ChoiceType::STEP) => {
+            let mut common = Vec::new();
+            for value in values.alternatives() {
+                if other_values.contains(value)? {
+                    common.push(value.to_vec());
+                }
+            }
+            (ChoiceType::ENUM, common)
+        }
+        (ChoiceType::RANGE | ChoiceType::STEP, ChoiceType::NONE | ChoiceType::ENUM) => {
+            let mut common = Vec::new();
+            for value in other_values.alternatives() {
+                if values.contains(value)? {
+                    common.push(value.to_vec());
+                }
+            }
+            (ChoiceType::ENUM, common)
+        }
+        (ChoiceType::RANGE | ChoiceType::STEP, ChoiceType::RANGE | ChoiceType::STEP) => {
+            // Steps cannot be combined, the same as spa_pod_filter does not support them
+            let step = match (values.step()?, other_values.step()?) {
+                (Some(step), Some(other_step)) => {
+                    if compare(type_, step, other_step) != Some(Ordering::Equal) {
+                        return Err(PodError::UnexpectedChoiceType(
+                            values.choice,
+                            other_values.choice,
+                        ));
+                    }
+                    Some(step)
+                }
+                (step, other_step) => step.or(other_step),
+            };
+            let (min, max) = values.bounds()?;
+            let (other_min, other_max) = other_values.bounds()?;
+            let min = match compare(type_, min, other_min) {
+                Some(Ordering::Less) => other_min,
+                Some(_) => min,
+                None => return Ok(false),
+            };
+            let max = match compare(type_, max, other_max) {
+                Some(Ordering::Greater) => other_max,
+                Some(_) => max,
+                None => return Ok(false),
+            };
+            if compare(type_, min, max) == Some(Ordering::Greater) {
+                return Ok(false);
+            }
+            match step {
+                Some(step) => (
+                    ChoiceType::STEP,
+                    vec![min.to_vec(), max.to_vec(), step.to_vec()],
+                ),
+                None => (ChoiceType::RANGE, vec![min.to_vec(), max.to_vec()]),
+            }
+        }
+        (ChoiceType::FLAGS, ChoiceType::FLAGS) => {
+            match mask_flags(type_, values.default(), other_values.default()) {
+                Some(mask) => (ChoiceType::FLAGS, vec![mask]),
+                None => return Ok(false),
+            }
+        }
+        (choice, other_choice) => {
+            return Err(PodError::UnexpectedChoiceType(choice, other_choice));
+        }
+    };
+    if result.is_empty() {
+        return Ok(false);
+    }
+
+    let flags = prop.flags & other.flags;
+    let default = values.default();
+    match choice {
+        ChoiceType::ENUM => {
+            let default = result
+                .iter()
+                .find(|value| compare(type_, default, value) == Some(Ordering::Equal))
+                .unwrap_or(&result[0])
+                .as_slice();
+            if result.len() == 1 {
+                if prop.pod.type_() != Type::CHOICE && other.pod.type_() != Type::CHOICE {
+                    copy_prop_value(buffer, prop.key, flags, type_, default)
+                } else {
+                    write_choice_prop(buffer, prop.key, flags, ChoiceType::NONE, type_, &[default])
+                }
+            } else {
+                let mut choice_values = vec![default];
+                choice_values.extend(result.iter().map(Vec::as_slice));
+                write_choice_prop(buffer, prop.key, flags, choice, type_, &choice_values)
+            }
+        }
+        ChoiceType::RANGE | ChoiceType::STEP => {
+            let (min, max) = (result[0].as_slice(), result[1].as_slice());
+            let default = if compare(type_, default, min) == Some(Ordering::Less) {
+                min
+            } else if compare(type_, default, max) == Some(Ordering::Greater) {
+                max
+            } else {
+                default
+            };
+            let mut choice_values = vec![default, min, max];
+            choice_values.extend(result.get(2).map(Vec::as_slice));
+            write_choice_prop(buffer, prop.key, flags, choice, type_, &choice_values)
+        }
+        _ => {
+            let mask = result[0].as_slice();
+            write_choice_prop(buffer, prop.key, flags, choice, type_, &[mask, mask])
+        }
+    }?;
+    Ok(true)
+}
+
+pub(super) fn write_prop_header<W>(buffer: &mut W, key: u32, flags: PodPropFlags) -> PodResult<()>
+where
+    W: Write + Seek,
+{
+    write_value(buffer, &key)?;
+    write_value(buffer, &flags.bits())
+}
+
+pub(super) fn copy_prop_value<W>(
+    buffer: &mut W,
+    key: u32,
+    flags: PodPropFlags,
+    type_: Type,
+    value: &[u8],
+) -> PodResult<()>
+where
+    W: Write + Seek,
+{
+    write_prop_header(buffer, key, flags)?;
+    write_header(buffer, value.len() as u32, type_)?;
+    buffer.write_all(value)?;
+    write_align_padding(buffer)
+}
+
+fn write_choice_prop<W>(
+    buffer: &mut W,
+    key: u32,
+    flags: PodPropFlags,
+    choice: ChoiceType,
+    type_: Type,
+    values: &[&[u8]],
+) -> PodResult<()>
+where
+    W: Write + Seek,
+{
+    let size = values[0].len();
+    write_prop_header(buffer, key, flags)?;
+    write_header(
+        buffer,
+        (size_of::<spa_sys::spa_pod_choice_body>() + size * values.len()) as u32,
+        Type::CHOICE,
+    )?;
+    write_value(
+        buffer,
+        &spa_sys::spa_pod_choice_body {
+            type_: choice.raw,
+            flags: 0,
+            child: spa_sys::spa_pod {
+                size: size as u32,
+                type_: type_.raw,
+            },
+        },
+    )?;
+    for value in values {
+        buffer.write_all(value)?;
+    }
+    write_align_padding(buffer)
+}
+
+/// Compare the raw values with the given type, the same as `spa_pod_compare_value`.
+/// The values of the unsupported types can only be equal.
+pub(super) fn compare(type_: Type, value: &[u8], other: &[u8]) -> Option<Ordering> {
+    match type_ {
+        Type::BOOL | Type::INT => i32::from_ne_bytes(read(value, 0).ok()?)
+            .partial_cmp(&i32::from_ne_bytes(read(other, 0).ok()?)),
+        Type::ID => read_u32(value, 0)
+            .ok()?
+            .partial_cmp(&read_u32(other, 0).ok()?),
+        Type::LONG => i64::from_ne_bytes(read(value, 0).ok()?)
+            .partial_cmp(&i64::from_ne_bytes(read(other, 0).ok()?)),
+        Type::FLOAT => f32::from_ne_bytes(read(value, 0).ok()?)
+            .partial_cmp(&f32::from_ne_bytes(read(other, 0).ok()?)),
+        Type::DOUBLE => f64::from_ne_bytes(read(value, 0).ok()?)
+            .partial_cmp(&f64::from_ne_bytes(read(other, 0).ok()?)),
+        Type::RECTANGLE => {
+            let (width, height) = (read_u32(value, 0).ok()?, read_u32(value, 4).ok()?);
+            let (other_width, other_height) = (read_u32(other, 0).ok()?, read_u32(other, 4).ok()?);
+            if width == other_width && height == other_height {
+                Some(Ordering::Equal)
+            } else if width < other_width || height < other_height {
+                Some(Ordering::Less)
+            } else {
+                Some(Ordering::Greater)
+            }
+        }
+        Type::FRACTION => {
+            let (num, denom) = (
+                read_u32(value, 0).ok()? as u64,
+                read_u32(value, 4).ok()? as u64,
+            );
+            let (other_num, other_denom) = (
+                read_u32(other, 0).ok()? as u64,
+                read_u32(other, 4).ok()? as u64,
+            );
+            (num * other_denom).partial_cmp(&(other_num * denom))
+        }
+        Type::STRING => {
+            let end = value.iter().position(|b| *b == 0).unwrap_or(value.len());
+            let other_end = other.iter().position(|b| *b == 0).unwrap_or(other.len());
+            value[..end].partial_cmp(&other[..other_end])
+        }
+        _ => (value == other).then_some(Ordering::Equal),
+    }
+}
+
+fn is_step_of(type_: Type, value: &[u8], step: &[u8]) -> bool {
+    fn is_multiple<T>(value: Option<T>, step: Option<T>) -> bool
+    where
+        T: Rem<Output = T> + PartialEq + Default + Copy,
+    {
+        match (value, step) {
+            (Some(value), Some(step)) => step != T::default() && value % step == T::default(),
+            _ => false,
+        }
+    }
+    match type_ {
+        Type::INT => is_multiple(
+            read(value, 0).ok().map(i32::from_ne_bytes),
+            read(step, 0).ok().map(i32::from_ne_bytes),
+        ),
+        Type::LONG => is_multiple(
+            read(value, 0).ok().map(i64::from_ne_bytes),
+            read(step, 0).ok().map(i64::from_ne_bytes),
+        ),
+        Type::RECTANGLE => {
+            is_multiple(read_u32(value, 0).ok(), read_u32(step, 0).ok())
+                && is_multiple(read_u32(value, 4).ok(), read_u32(step, 4).ok())
+        }
+        _ => false,
+    }
+}
+
+fn mask_flags(type_: Type, value: &[u8], other: &[u8]) -> Option<Vec<u8>> {
+    match type_ {
+        Type::INT => {
+            let mask =
+                i32::from_ne_bytes(read(value, 0).ok()?) & i32::from_ne_bytes(read(other, 0).ok()?);
+            (mask != 0).then(|| mask.to_ne_bytes().to_vec())
+        }
+        Type::LONG => {
+            let mask =
+                i64::from_ne_bytes(read(value, 0).ok()?) & i64::from_ne_bytes(read(other, 0).ok()?);
+            (mask != 0).then(|| mask.to_ne_bytes().to_vec())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+fn audio_enum_format(
+    rate: Option<ChoiceStructType<PodIntRef>>,
+    channels: Option<ChoiceStructType<PodIntRef>>,
+) -> AllocPod<PodObjectRef> {
+    let mut builder = audio_enum_format_builder();
+    if let Some(rate) = rate {
+        builder = builder.audio_rate(rate);
+    }
+    if let Some(channels) = channels {
+        builder = builder.audio_channels(channels);
+    }
+    builder.build().unwrap()
+}
+
+#[cfg(test)]
+fn audio_rate(pod: &PodObjectRef) -> Option<ChoiceStructType<PodIntRef>> {
+    if let ObjectType::OBJECT_ENUM_FORMAT(props) = pod.param_value(ParamType::ENUM_FORMAT).unwrap()
+    {
+        for prop in props {
+            if let ObjectEnumFormatType::AUDIO_RATE(rate) = prop.value().unwrap() {
+                return Some(rate.value().unwrap());
+            }
+        }
+        None
+    } else {
+        panic!()
+    }
+}
+
+#[test]
+fn test_filter_range_enum() {
+    let range = audio_enum_format(
+        Some(ChoiceStructType::RANGE(PodRangeValue::new(
+            48000, 8000, 48000,
+        ))),
+        None,
+    );
+    let enum_ = audio_enum_format(
+        Some(ChoiceStructType::ENUM(PodEnumValue::new(
+            96000,
+            vec![96000, 44100, 22050],
+        ))),
+        None,
+    );
+
+    let filtered = range.as_pod().filter(enum_.as_pod()).unwrap().unwrap();
+    assert_eq!(filtered.as_pod().body_id(), ParamType::ENUM_FORMAT.raw);
+    if let Some(ChoiceStructType::ENUM(rate)) = audio_rate(filtered.as_pod()) {
+        assert_eq!(rate, PodEnumValue::new(44100, vec![44100, 22050]));
+    } else {
+        panic!()
+    }
+}
+
+#[test]
+fn test_filter_enum_enum() {
+    let first = audio_enum_format(
+        Some(ChoiceStructType::ENUM(PodEnumValue::new(
+            48000,
+            vec![48000, 44100, 22050],
+        ))),
+        None,
+    );
+    let second = audio_enum_format(
+        Some(ChoiceStructType::ENUM(PodEnumValue::new(
+            44100,
+            vec![96000, 48000, 44100],
+        ))),
+        None,
+    );
+
+    let filtered = first.as_pod().filter(second.as_pod()).unwrap().unwrap();
+    if let Some(ChoiceStructType::ENUM(rate)) = audio_rate(filtered.as_pod()) {
+        assert_eq!(rate, PodEnumValue::new(48000, vec![48000, 44100]));
+    } else {
+        panic!()
+    }
+
+    let fixed = audio_enum_format(Some(ChoiceStructType::NONE(44100)), None);
+    let filtered = first.as_pod().filter(fixed.as_pod()).unwrap().unwrap();
+    if let Some(ChoiceStructType::NONE(rate)) = audio_rate(filtered.as_pod()) {
+        assert_eq!(rate, 44100);
+    } else {
+        panic!()
+    }
+}
+
+#[test]
+fn test_filter_range_range() {
+    let first = audio_enum_format(
+        Some(ChoiceStructType::RANGE(PodRangeValue::new(
+            8000, 8000, 48000,
+        ))),
+        None,
+    );
+    let second = audio_enum_format(
+        Some(ChoiceStructType::RANGE(PodRangeValue::new(
+            96000, 44100, 192000,
+        ))),
+        None,
+    );
+
+    let filtered = first.as_pod().filter(second.as_pod()).unwrap().unwrap();
+    if let Some(ChoiceStructType::RANGE(rate)) = audio_rate(filtered.as_pod()) {
+        assert_eq!(rate, PodRangeValue::new(44100, 44100, 48000));
+    } else {
+        panic!()
+    }
+}
+
+#[test]
+fn test_filter_incompatible() {
+    let first = audio_enum_format(
+        Some(ChoiceStructType::RANGE(PodRangeValue::new(
+            8000, 8000, 22050,
+        ))),
+        None,
+    );
+    let second = audio_enum_format(
+        Some(ChoiceStructType::ENUM(PodEnumValue::new(
+            48000,
+            vec![48000, 44100],
+        ))),
+        None,
+    );
+
+    assert!(first.as_pod().filter(second.as_pod()).unwrap().is_none());
+}
+
+#[test]
+fn test_filter_missing_prop() {
+    let first = audio_enum_format(Some(ChoiceStructType::NONE(48000)), None);
+    let second = audio_enum_format(None, Some(ChoiceStructType::NONE(2)));
+
+    let filtered = first.as_pod().filter(second.as_pod()).unwrap().unwrap();
+    if let Some(ChoiceStructType::NONE(rate)) = audio_rate(filtered.as_pod()) {
+        assert_eq!(rate, 48000);
+    } else {
+        panic!()
+    }
+    if let ObjectType::OBJECT_ENUM_FORMAT(props) = filtered
+        .as_pod()
+        .param_value(ParamType::ENUM_FORMAT)
+        .unwrap()
+    {
+        assert_eq!(props.count(), 4);
+    } else {
+        panic!()
+    }
+}
+
+#[test]
+fn test_filter_step() {
+    let step = audio_enum_format(
+        Some(ChoiceStructType::STEP(PodStepValue::new(
+            48000, 8000, 96000, 8000,
+        ))),
+        None,
+    );
+    let range = audio_enum_format(
+        Some(ChoiceStructType::RANGE(PodRangeValue::new(
+            44100, 16000, 64000,
+        ))),
+        None,
+    );
+
+    let filtered = step.as_pod().filter(range.as_pod()).unwrap().unwrap();
+    if let Some(ChoiceStructType::STEP(rate)) = audio_rate(filtered.as_pod()) {
+        assert_eq!(rate, PodStepValue::new(48000, 16000, 64000, 8000));
+    } else {
+        panic!()
+    }
+
+    let filtered = step.as_pod().filter(step.as_pod()).unwrap().unwrap();
+    if let Some(ChoiceStructType::STEP(rate)) = audio_rate(filtered.as_pod()) {
+        assert_eq!(rate, PodStepValue::new(48000, 8000, 96000, 8000));
+    } else {
+        panic!()
+    }
+
+    let other_step = audio_enum_format(
+        Some(ChoiceStructType::STEP(PodStepValue::new(
+            48000, 8000, 96000, 4000,
+        ))),
+        None,
+    );
+    assert!(matches!(
+        step.as_pod().filter(other_step.as_pod()),
+        Err(PodError::UnexpectedChoiceType(
+            ChoiceType::STEP,
+            ChoiceType::STEP
+        ))
+    ));
+}
@@ -0,0 +1,237 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Object pods fixation, the same as `spa_pod_fixate` does for the objects.
+//!
+use std::cmp::Ordering;
+use std::io::{Cursor, Seek, Write};
+
+use crate::spa::pod::choice::{ChoiceType, ChoiceValueType, PodChoiceRef};
+use crate::spa::pod::id::PodIdRef;
+use crate::spa::pod::object::filter::{compare, copy_prop_value, write_prop_header, Values};
+use crate::spa::pod::object::{write_object, PodObjectRef, PodPropFlags};
+use crate::spa::pod::pod_buf::{AllocPod, PodBuf};
+use crate::spa::pod::restricted::{BasicTypePod, CloneTo, WritePod};
+use crate::spa::pod::{
+    PodBoolRef, PodDoubleRef, PodFloatRef, PodFractionRef, PodIntRef, PodLongRef, PodRectangleRef,
+    PodResult,
+};
+use crate::spa::type_::Type;
+
+#[cfg(test)]
+use crate::spa::{
+    param::ParamType,
+    pod::choice::{enum_::PodEnumValue, range::PodRangeValue, ChoiceStructType},
+    pod::object::enum_format::ObjectEnumFormatBuilder,
+    pod::object::format::{Format, ObjectFormatType},
+    pod::object::{audio_enum_format_builder, ObjectType},
+    pod::PodValue,
+};
+
+impl PodObjectRef {
+    /// Replace every choice property with its default value.
+    ///
+    /// Returns the new object with plain values and without choice wrappers,
+    /// the properties that are not choices or are flagged with [PodPropFlags::DONT_FIXATE]
+    /// are copied as is.
+    pub fn fixate(&self) -> PodResult<AllocPod<PodObjectRef>> {
+        self.fixate_props(None)
+    }
+
+    /// Replace every choice property with the value from the `preferred` object,
+    /// or with its default value when the preferred value does not fit the choice.
+    ///
+    /// The preferred value is used when it is one of the Enum alternatives,
+    /// fits the Step choice or is clamped into the Range bounds.
+    pub fn fixate_with(&self, preferred: &PodObjectRef) -> PodResult<AllocPod<PodObjectRef>> {
+        self.fixate_props(Some(preferred))
+    }
+
+    fn fixate_props(&self, preferred: Option<&PodObjectRef>) -> PodResult<AllocPod<PodObjectRef>> {
+        let preferred_props = match preferred {
+            Some(preferred) => preferred.raw_props()?,
+            None => Vec::new(),
+        };
+
+        let mut content = Cursor::new(Vec::new());
+        for prop in self.raw_props()? {
+            if prop.pod.type_() != Type::CHOICE || prop.flags.contains(PodPropFlags::DONT_FIXATE) {
+                write_prop_header(&mut content, prop.key, prop.flags)?;
+                prop.pod.clone_to(&mut content)?;
+                continue;
+            }
+            let values = Values::from_pod(prop.pod)?;
+            let preferred_value = match preferred_props.iter().find(|p| p.key == prop.key) {
+                Some(preferred_prop) => {
+                    preferred_value(&values, &Values::from_pod(preferred_prop.pod)?)?
+                }
+                None => None,
+            };
+            if let Some(value) = preferred_value {
+                copy_prop_value(&mut content, prop.key, prop.flags, values.type_, value)?;
+            } else {
+                let choice: &PodChoiceRef = unsafe { prop.pod.cast_unchecked() };
+                write_prop_header(&mut content, prop.key, prop.flags)?;
+                write_default(&mut content, &choice.choice_value()?)?;
+            }
+        }
+
+        let mut buf = PodBuf::<PodObjectRef>::new();
+        write_object(
+            &mut buf,
+            self.body_type(),
+            self.body_id(),
+            content.get_ref().as_slice(),
+        )?;
+        Ok(buf.into_pod())
+    }
+}
+
+impl AllocPod<PodObjectRef> {
+    /// See [PodObjectRef::fixate]
+    pub fn fixate(&self) -> PodResult<AllocPod<PodObjectRef>> {
+        self.as_pod().fixate()
+    }
+
+    /// See [PodObjectRef::fixate_with]
+    pub fn fixate_with(&self, preferred: &PodObjectRef) -> PodResult<AllocPod<PodObjectRef>> {
+        self.as_pod().fixate_with(preferred)
+    }
+}
+
+/// Preferred value that fits the choice, if any
+fn preferred_value<'a>(values: &Values<'a>, preferred: &Values<'a>) -> PodResult<Option<&'a [u8]>> {
+    if values.type_ != preferred.type_ || values.size() != preferred.size() {
+        return Ok(None);
+    }
+    let type_ = values.type_;
+    let value = preferred.default();
+    Ok(match values.choice {
+        ChoiceType::ENUM => values
+            .alternatives()
+            .iter()
+            .any(|v| compare(type_, v, value) == Some(Ordering::Equal))
+            .then_some(value),
+        ChoiceType::RANGE => {
+            let (min, max) = values.bounds()?;
+            match (compare(type_, value, min), compare(type_, value, max)) {
+                (Some(Ordering::Less), _) => Some(min),
+                (_, Some(Ordering::Greater)) => Some(max),
+                (Some(_), Some(_)) => Some(value),
+                _ => None,
+            }
+        }
+        ChoiceType::STEP => values.contains(value)?.then_some(value),
+        _ => None,
+    })
+}
+
+fn write_default<W>(buffer: &mut W, value: &ChoiceValueType) -> PodResult<()>
+where
+    W: Write + Seek,
+{
+    match value {
+        ChoiceValueType::BOOL(v) => PodBoolRef::write_pod(buffer, v.default()),
+        ChoiceValueType::ID(v) => <PodIdRef>::write_pod(buffer, v.default()),
+        ChoiceValueType::INT(v) => PodIntRef::write_pod(buffer, v.default()),
+        ChoiceValueType::LONG(v) => PodLongRef::write_pod(buffer, v.default()),
+        ChoiceValueType::FLOAT(v) => PodFloatRef::write_pod(buffer, v.default()),
+        ChoiceValueType::DOUBLE(v) => PodDoubleRef::write_pod(buffer, v.default()),
+        ChoiceValueType::RECTANGLE(v) => PodRectangleRef::write_pod(buffer, v.default()),
+        ChoiceValueType::FRACTION(v) => PodFractionRef::write_pod(buffer, v.default()),
+    }
+}
+
+#[cfg(test)]
+fn audio_enum_format() -> AllocPod<PodObjectRef> {
+    audio_enum_format_builder()
+        .audio_rate(ChoiceStructType::RANGE(PodRangeValue::new(
+            44100, 8000, 96000,
+        )))
+        .audio_channels(ChoiceStructType::ENUM(PodEnumValue::new(2, vec![1, 2])))
+        .build()
+        .unwrap()
+}
+
+#[cfg(test)]
+fn rate_and_channels(pod: &PodObjectRef) -> (i32, i32) {
+    let (mut rate, mut channels) = (0, 0);
+    if let ObjectType::OBJECT_FORMAT(props) = pod.param_value(ParamType::FORMAT).unwrap() {
+        for prop in props {
+            match prop.value().unwrap() {
+                ObjectFormatType::AUDIO_RATE(v) => rate = v.value().unwrap(),
+                ObjectFormatType::AUDIO_CHANNELS(v) => channels = v.value().unwrap(),
+                _ => {}
+            }
+        }
+    } else {
+        panic!()
+    }
+    (rate, channels)
+}
+
+#[test]
+fn test_fixate() {
+    let enum_format = audio_enum_format();
+    let fixated = enum_format.fixate().unwrap();
+
+    assert_eq!(fixated.as_pod().body_id(), ParamType::ENUM_FORMAT.raw);
+    assert_eq!(rate_and_channels(fixated.as_pod()), (44100, 2));
+}
+
+#[test]
+fn test_fixate_with() {
+    let enum_format = audio_enum_format();
+    let preferred = ObjectEnumFormatBuilder::default()
+        .body_id(ParamType::ENUM_FORMAT.into())
+        .audio_rate(ChoiceStructType::NONE(48000))
+        .audio_channels(ChoiceStructType::NONE(1))
+        .build()
+        .unwrap();
+    let fixated = enum_format.fixate_with(preferred.as_pod()).unwrap();
+    assert_eq!(rate_and_channels(fixated.as_pod()), (48000, 1));
+
+    let preferred = ObjectEnumFormatBuilder::default()
+        .body_id(ParamType::ENUM_FORMAT.into())
+        .audio_rate(ChoiceStructType::NONE(192000))
+        .audio_channels(ChoiceStructType::NONE(6))
+        .build()
+        .unwrap();
+    let fixated = enum_format.fixate_with(preferred.as_pod()).unwrap();
+    assert_eq!(rate_and_channels(fixated.as_pod()), (96000, 2));
+}
+
+#[test]
+fn test_fixate_dont_fixate() {
+    let enum_format = audio_enum_format();
+    let mut content = Cursor::new(Vec::new());
+    for prop in enum_format.as_pod().raw_props().unwrap() {
+        let mut flags = prop.flags;
+        if prop.key == Format::AUDIO_RATE.raw {
+            flags |= PodPropFlags::DONT_FIXATE;
+        }
+        write_prop_header(&mut content, prop.key, flags).unwrap();
+        prop.pod.clone_to(&mut content).unwrap();
+    }
+    let mut buf = PodBuf::<PodObjectRef>::new();
+    write_object(
+        &mut buf,
+        enum_format.as_pod().body_type(),
+        enum_format.as_pod().body_id(),
+        content.get_ref().as_slice(),
+    )
+    .unwrap();
+    let enum_format = buf.into_pod();
+
+    let fixated = enum_format.fixate().unwrap();
+    let props = fixated.as_pod().raw_props().unwrap();
+    let prop_type = |key: Format| {
+        props
+            .iter()
+            .find(|prop| prop.key == key.raw)
+            .map(|prop| prop.pod.type_())
+    };
+    assert_eq!(prop_type(Format::AUDIO_RATE), Some(Type::CHOICE));
+    assert_eq!(prop_type(Format::AUDIO_CHANNELS), Some(Type::INT));
+}
@@ -8,6 +8,7 @@ use pipewire_wrapper_proc_macro::object_type_impl;
 use crate::enum_wrapper;
 use crate::spa::pod::array::PodArrayRef;
 use crate::spa::pod::id::{PodIdRef, PodIdType};
+use crate::spa::pod::names;
 use crate::spa::pod::object::prop::AudioIec958Codec;
 use crate::spa::pod::object::{PodPropKeyType, PodPropRef};
 use crate::spa::pod::{
@@ -280,7 +281,15 @@ enum_wrapper!(
     STREAM: spa_sys::SPA_MEDIA_TYPE_stream,
     APPLICATION: spa_sys::SPA_MEDIA_TYPE_application,
 );
-impl PodIdType for MediaType {}
+impl PodIdType for MediaType {
+    fn type_info_name(&self) -> Option<String> {
+        names::id_name(names::MEDIA_TYPE, self.name()?)
+    }
+
+    fn from_type_info_name(name: &str) -> Option<Self> {
+        Self::from_name(&names::id_const_name(names::MEDIA_TYPE, name)?)
+    }
+}
 
 enum_wrapper!(
     MediaSubType,
@@ -325,7 +334,15 @@ enum_wrapper!(
     _START_APPLICATION: spa_sys::SPA_MEDIA_SUBTYPE_START_Application,
     CONTROL: spa_sys::SPA_MEDIA_SUBTYPE_control,
 );
-impl PodIdType for MediaSubType {}
+impl PodIdType for MediaSubType {
+    fn type_info_name(&self) -> Option<String> {
+        names::id_name(names::MEDIA_SUBTYPE, self.name()?)
+    }
+
+    fn from_type_info_name(name: &str) -> Option<Self> {
+        Self::from_name(&names::id_const_name(names::MEDIA_SUBTYPE, name)?)
+    }
+}
 
 enum_wrapper!(
     AudioFormat,
@@ -407,7 +424,15 @@ enum_wrapper!(
     F32_OE: spa_sys::SPA_AUDIO_FORMAT_F32_OE,
     F64_OE: spa_sys::SPA_AUDIO_FORMAT_F64_OE,
 );
-impl PodIdType for AudioFormat {}
+impl PodIdType for AudioFormat {
+    fn type_info_name(&self) -> Option<String> {
+        names::id_name(names::AUDIO_FORMAT, self.name()?)
+    }
+
+    fn from_type_info_name(name: &str) -> Option<Self> {
+        Self::from_name(&names::id_const_name(names::AUDIO_FORMAT, name)?)
+    }
+}
 
 enum_wrapper!(
     VideoFormat,
@@ -8,6 +8,7 @@ use pipewire_wrapper_proc_macro::object_type_impl;
 use crate::enum_wrapper;
 use crate::spa::pod::array::PodArrayRef;
 use crate::spa::pod::id::{PodIdRef, PodIdType};
+use crate::spa::pod::names;
 use crate::spa::pod::object::{PodPropKeyType, PodPropRef};
 use crate::spa::pod::string::PodStringRef;
 use crate::spa::pod::struct_::PodStructRef;
@@ -234,7 +235,15 @@ impl PodIdType for Prop {}
 
 impl PodIdType for BluetoothAudioCodec {}
 
-impl PodIdType for AudioChannel {}
+impl PodIdType for AudioChannel {
+    fn type_info_name(&self) -> Option<String> {
+        names::id_name(names::AUDIO_CHANNEL, self.name()?)
+    }
+
+    fn from_type_info_name(name: &str) -> Option<Self> {
+        Self::from_name(&names::id_const_name(names::AUDIO_CHANNEL, name)?)
+    }
+}
 
 impl PodIdType for AudioIec958Codec {}
 
@@ -181,3 +181,34 @@ where
     let end_pos = buffer.stream_position()?;
     Ok((end_pos - start_pos) as usize)
 }
+
+/// Read `N` bytes at the `offset`, fails if the `bytes` are too short.
+pub fn read<const N: usize>(bytes: &[u8], offset: usize) -> PodResult<[u8; N]> {
+    bytes
+        .get(offset..)
+        .and_then(|b| b.get(..N))
+        .and_then(|b| b.try_into().ok())
+        .ok_or(PodError::DataIsTooShort(
+            offset.saturating_add(N),
+            bytes.len(),
+        ))
+}
+
+/// Read [u32] at the `offset`, fails if the `bytes` are too short.
+pub fn read_u32(bytes: &[u8], offset: usize) -> PodResult<u32> {
+    read(bytes, offset).map(u32::from_ne_bytes)
+}
+
+/// Read the pod header at the `offset`.
+/// Returns the pod type, the pod body and the aligned offset of the next pod,
+/// fails if the header or the body don't fit into the `bytes`.
+pub fn read_pod(bytes: &[u8], offset: usize) -> PodResult<(Type, &[u8], usize)> {
+    let size = read_u32(bytes, offset)? as usize;
+    let type_ = Type::from_raw(read_u32(bytes, offset + 4)?);
+    let start = offset + size_of::<spa_sys::spa_pod>();
+    let end = start.saturating_add(size);
+    let body = bytes
+        .get(start..end)
+        .ok_or(PodError::DataIsTooShort(end, bytes.len()))?;
+    Ok((type_, body, end.next_multiple_of(POD_ALIGN)))
+}
@@ -0,0 +1,1232 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+//! [Serialize] and [Deserialize] implementations for the pods, enabled by the `serde` feature.
+//!
+//! The layout follows the `pw-dump` output:
+//! * `None`, `Bool`, `Int`, `Float`, `String` and `Struct` pods are plain values,
+//!   the integers are always `Int` and the numbers with the fraction are always `Float`;
+//! * `Rectangle` and `Fraction` are `{ "width", "height" }` and `{ "num", "denom" }` maps;
+//! * choices are maps with the `default`, `min`, `max`, `step`, `alt1`.. or `flag1`.. labels;
+//! * object property keys and ids are the short SPA type info names, if they are known;
+//! * other pods are maps with the single type key, like `{ "Long": 1 }`.
+//!
+//! ```json
+//! {
+//!   "Object": {
+//!     "type": "Spa:Pod:Object:Param:Format",
+//!     "id": "EnumFormat",
+//!     "properties": {
+//!       "mediaType": "audio",
+//!       "mediaSubtype": "raw",
+//!       "format": { "default": "F32LE", "alt1": "F32LE", "alt2": "S16LE" },
+//!       "rate": { "default": 48000, "min": 1, "max": 384000 },
+//!       "channels": 2
+//!     }
+//!   }
+//! }
+//! ```
+//! The values without the plain form are tagged, so any serialized pod can be deserialized
+//! back to the same [AllocPod]<[PodRef]>: ids without names are `{ "Id": 1 }`, strings
+//! looking like the id names are `{ "String": "audio" }`, the non-finite floats are
+//! `{ "Float": "NaN" }` and the Enum or Flags choices without alternatives have the `type` label.
+//!
+use std::fmt::Formatter;
+use std::io::{Cursor, Seek, Write};
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::slice;
+
+use serde::de::{Error as _, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Error as _, SerializeMap};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::spa::pod::array::PodArrayRef;
+use crate::spa::pod::bitmap::PodBitmapRef;
+use crate::spa::pod::bytes::PodBytesRef;
+use crate::spa::pod::choice::enum_::PodEnumRef;
+use crate::spa::pod::choice::flags::PodFlagsRef;
+use crate::spa::pod::choice::none::PodNoneRef;
+use crate::spa::pod::choice::range::PodRangeRef;
+use crate::spa::pod::choice::step::PodStepRef;
+use crate::spa::pod::choice::{ChoiceType, PodChoiceRef};
+use crate::spa::pod::control;
+use crate::spa::pod::id::{PodIdRef, PodIdType};
+use crate::spa::pod::names;
+use crate::spa::pod::object::format::{Format, MediaType};
+use crate::spa::pod::object::prop::ObjectPropType;
+use crate::spa::pod::object::{
+    prop_id_value_from_name, prop_id_value_name, prop_key_names, PodObjectRef,
+};
+use crate::spa::pod::pod_buf::{AllocPod, PodBuf};
+use crate::spa::pod::pointer::PodPointerRef;
+use crate::spa::pod::restricted::{
+    read, read_pod, read_u32, write_align_padding, write_header, write_value, BasicTypePod,
+    PodRawValue,
+};
+use crate::spa::pod::sequence::PodSequenceRef;
+use crate::spa::pod::string::PodStringRef;
+use crate::spa::pod::struct_::PodStructRef;
+use crate::spa::pod::{
+    BasicType, PodBoolRef, PodDoubleRef, PodError, PodFdRef, PodFloatRef, PodFractionRef,
+    PodIntRef, PodLongRef, PodRectangleRef, PodRef, PodResult, PodValue, Upcast,
+};
+use crate::spa::type_::Type;
+use crate::wrapper::RawWrapper;
+
+#[cfg(test)]
+use crate::spa::pod::{
+    choice::{enum_::PodEnumValue, range::PodRangeValue, ChoiceStructType},
+    object::audio_enum_format_builder,
+    object::format::AudioFormat,
+};
+
+/// Owned pod representation, used as serde data model.
+#[derive(Debug, Clone, PartialEq)]
+enum PodData {
+    None,
+    Bool(bool),
+    Id(u32),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    /// String that is tagged to not be confused with the id name
+    TaggedString(String),
+    Bytes(Vec<u8>),
+    Rectangle {
+        width: u32,
+        height: u32,
+    },
+    Fraction {
+        num: u32,
+        denom: u32,
+    },
+    Bitmap(Vec<u8>),
+    Array(ArrayData),
+    Struct(Vec<PodData>),
+    Object(ObjectData),
+    Sequence(SequenceData),
+    Pointer(PointerData),
+    Fd(i64),
+    Choice(ChoiceData),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ArrayData {
+    #[serde(rename = "type")]
+    child_type: Named,
+    values: Vec<PodData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ObjectData {
+    #[serde(rename = "type")]
+    type_: Named,
+    id: Named,
+    properties: Entries<PodData>,
+    #[serde(default, skip_serializing_if = "Entries::is_empty")]
+    flags: Entries<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SequenceData {
+    unit: u32,
+    controls: Vec<ControlData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ControlData {
+    offset: u32,
+    #[serde(rename = "type")]
+    type_: Named,
+    value: PodData,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PointerData {
+    #[serde(rename = "type")]
+    type_: Named,
+    value: usize,
+}
+
+/// Choice with the default value first, serialized as the map with the value labels.
+#[derive(Debug, Clone, PartialEq)]
+struct ChoiceData {
+    type_: ChoiceType,
+    values: Vec<PodData>,
+}
+
+/// SPA type info name or the raw value if the name is unknown.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Named {
+    Raw(u32),
+    Name(String),
+}
+
+/// Floating point number, the non-finite values are strings like `NaN` or `inf`.
+struct Real(f64);
+
+/// Map entries with the preserved order.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Entries<V>(Vec<(String, V)>);
+
+impl<V> Entries<V> {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<V: Serialize> Serialize for Entries<V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for Entries<V> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct EntriesVisitor<V>(PhantomData<V>);
+
+        impl<'de, V: Deserialize<'de>> Visitor<'de> for EntriesVisitor<V> {
+            type Value = Entries<V>;
+
+            fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+                f.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Entries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor(PhantomData))
+    }
+}
+
+impl Serialize for Real {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.0.is_finite() {
+            serializer.serialize_f64(self.0)
+        } else {
+            serializer.serialize_str(&self.0.to_string())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Real {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct RealVisitor;
+
+        impl<'de> Visitor<'de> for RealVisitor {
+            type Value = Real;
+
+            fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+                f.write_str("a number or NaN, inf, -inf")
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(Real(v as f64))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(Real(v as f64))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Self::Value, E> {
+                Ok(Real(v))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map(Real).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(RealVisitor)
+    }
+}
+
+/// Map with the single type key, like `{ "Long": 1 }`
+fn tagged<S, T>(serializer: S, tag: &str, value: &T) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize + ?Sized,
+{
+    let mut map = serializer.serialize_map(Some(1))?;
+    map.serialize_entry(tag, value)?;
+    map.end()
+}
+
+impl Serialize for PodData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            PodData::None => serializer.serialize_unit(),
+            PodData::Bool(value) => serializer.serialize_bool(*value),
+            PodData::Id(value) => tagged(serializer, "Id", value),
+            PodData::Int(value) => serializer.serialize_i32(*value),
+            PodData::Long(value) => tagged(serializer, "Long", value),
+            PodData::Float(value) if value.is_finite() => serializer.serialize_f32(*value),
+            PodData::Float(value) => tagged(serializer, "Float", &Real(*value as f64)),
+            PodData::Double(value) => tagged(serializer, "Double", &Real(*value)),
+            PodData::String(value) => serializer.serialize_str(value),
+            PodData::TaggedString(value) => tagged(serializer, "String", value),
+            PodData::Bytes(value) => tagged(serializer, "Bytes", value),
+            PodData::Rectangle { width, height } => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("width", width)?;
+                map.serialize_entry("height", height)?;
+                map.end()
+            }
+            PodData::Fraction { num, denom } => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("num", num)?;
+                map.serialize_entry("denom", denom)?;
+                map.end()
+            }
+            PodData::Bitmap(value) => tagged(serializer, "Bitmap", value),
+            PodData::Array(value) => tagged(serializer, "Array", value),
+            PodData::Struct(fields) => fields.serialize(serializer),
+            PodData::Object(value) => tagged(serializer, "Object", value),
+            PodData::Sequence(value) => tagged(serializer, "Sequence", value),
+            PodData::Pointer(value) => tagged(serializer, "Pointer", value),
+            PodData::Fd(value) => tagged(serializer, "Fd", value),
+            PodData::Choice(choice) => {
+                let mut map = serializer.serialize_map(None)?;
+                // Enum and Flags without alternatives look like None
+                if matches!(choice.type_, ChoiceType::ENUM | ChoiceType::FLAGS)
+                    && choice.values.len() < 2
+                {
+                    let name = names::choice_type_name(choice.type_).unwrap_or_default();
+                    map.serialize_entry("type", names::short_name(name))?;
+                }
+                for (index, value) in choice.values.iter().enumerate() {
+                    map.serialize_entry(&choice_label(choice.type_, index), value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PodData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(PodDataVisitor)
+    }
+}
+
+struct PodDataVisitor;
+
+impl<'de> Visitor<'de> for PodDataVisitor {
+    type Value = PodData;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str("a pod value")
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(PodData::None)
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(PodData::None)
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(PodData::Bool(v))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        i32::try_from(v)
+            .map(PodData::Int)
+            .map_err(|_| E::custom(format_args!("{} is out of Int range, use Long", v)))
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        i32::try_from(v)
+            .map(PodData::Int)
+            .map_err(|_| E::custom(format_args!("{} is out of Int range, use Long", v)))
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(PodData::Float(v as f32))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(PodData::String(v.to_string()))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut fields = Vec::new();
+        while let Some(field) = seq.next_element()? {
+            fields.push(field);
+        }
+        Ok(PodData::Struct(fields))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let Some(key) = map.next_key::<String>()? else {
+            return Err(A::Error::invalid_length(0, &self));
+        };
+        let value = match key.as_str() {
+            "Id" => PodData::Id(map.next_value()?),
+            "Long" => PodData::Long(map.next_value()?),
+            "Float" => PodData::Float(map.next_value::<Real>()?.0 as f32),
+            "Double" => PodData::Double(map.next_value::<Real>()?.0),
+            "String" => PodData::TaggedString(map.next_value()?),
+            "Bytes" => PodData::Bytes(map.next_value()?),
+            "Bitmap" => PodData::Bitmap(map.next_value()?),
+            "Array" => PodData::Array(map.next_value()?),
+            "Object" => PodData::Object(map.next_value()?),
+            "Sequence" => PodData::Sequence(map.next_value()?),
+            "Pointer" => PodData::Pointer(map.next_value()?),
+            "Fd" => PodData::Fd(map.next_value()?),
+            "width" | "height" => {
+                let [width, height] = read_pair(map, key, &["width", "height"])?;
+                return Ok(PodData::Rectangle { width, height });
+            }
+            "num" | "denom" => {
+                let [num, denom] = read_pair(map, key, &["num", "denom"])?;
+                return Ok(PodData::Fraction { num, denom });
+            }
+            _ => return read_choice(map, key),
+        };
+        match map.next_key::<String>()? {
+            Some(other) => Err(A::Error::custom(format_args!(
+                "unexpected {} after {}",
+                other, key
+            ))),
+            None => Ok(value),
+        }
+    }
+}
+
+/// Rectangle or Fraction fields in any order
+fn read_pair<'de, A>(
+    mut map: A,
+    first: String,
+    fields: &'static [&'static str],
+) -> Result<[u32; 2], A::Error>
+where
+    A: MapAccess<'de>,
+{
+    let mut values = [None, None];
+    let mut key = Some(first);
+    while let Some(name) = key {
+        let index = fields
+            .iter()
+            .position(|field| *field == name)
+            .ok_or_else(|| A::Error::unknown_field(&name, fields))?;
+        values[index] = Some(map.next_value()?);
+        key = map.next_key()?;
+    }
+    match values {
+        [Some(first), Some(second)] => Ok([first, second]),
+        [None, _] => Err(A::Error::missing_field(fields[0])),
+        [_, None] => Err(A::Error::missing_field(fields[1])),
+    }
+}
+
+fn read_choice<'de, A>(mut map: A, first: String) -> Result<PodData, A::Error>
+where
+    A: MapAccess<'de>,
+{
+    let mut choice_type = None;
+    let mut entries = Vec::new();
+    let mut key = Some(first);
+    while let Some(label) = key {
+        if label == "type" {
+            let name: String = map.next_value()?;
+            let type_ = names::choice_type_from_name(&name)
+                .ok_or_else(|| A::Error::custom(PodError::UnknownName(name)))?;
+            choice_type = Some(type_);
+        } else {
+            entries.push((label, map.next_value()?));
+        }
+        key = map.next_key()?;
+    }
+    ChoiceData::from_entries(choice_type, entries)
+        .map(PodData::Choice)
+        .map_err(A::Error::custom)
+}
+
+/// Label of the choice value, like `default` or `alt1`
+fn choice_label(choice_type: ChoiceType, index: usize) -> String {
+    match (choice_type, index) {
+        (_, 0) => "default".to_string(),
+        (ChoiceType::ENUM, index) => format!("alt{}", index),
+        (ChoiceType::FLAGS, index) => format!("flag{}", index),
+        (_, 1) => "min".to_string(),
+        (_, 2) => "max".to_string(),
+        (_, 3) => "step".to_string(),
+        (_, index) => index.to_string(),
+    }
+}
+
+impl ChoiceData {
+    /// Choice from the labeled values, the type is guessed from the labels if not set
+    fn from_entries(
+        choice_type: Option<ChoiceType>,
+        mut entries: Vec<(String, PodData)>,
+    ) -> Result<Self, String> {
+        let has = |prefix: &str| entries.iter().any(|(label, _)| label.starts_with(prefix));
+        let type_ = match choice_type {
+            Some(choice_type) => choice_type,
+            None if has("step") => ChoiceType::STEP,
+            None if has("min") || has("max") => ChoiceType::RANGE,
+            None if has("alt") => ChoiceType::ENUM,
+            None if has("flag") => ChoiceType::FLAGS,
+            None => ChoiceType::NONE,
+        };
+        let count = match type_ {
+            ChoiceType::NONE => 1,
+            ChoiceType::RANGE => 3,
+            ChoiceType::STEP => 4,
+            ChoiceType::ENUM | ChoiceType::FLAGS => entries.len(),
+            _ => return Err(format!("unsupported choice type {:?}", type_)),
+        };
+        let mut values = Vec::with_capacity(count);
+        for index in 0..count.max(1) {
+            let label = choice_label(type_, index);
+            // Alternatives and flags are kept in the map order
+            let position = match type_ {
+                ChoiceType::ENUM | ChoiceType::FLAGS if index > 0 => {
+                    let prefix = if type_ == ChoiceType::ENUM {
+                        "alt"
+                    } else {
+                        "flag"
+                    };
+                    entries.iter().position(|(other, _)| {
+                        other.starts_with(prefix) && other[prefix.len()..].parse::<u32>().is_ok()
+                    })
+                }
+                _ => entries.iter().position(|(other, _)| *other == label),
+            };
+            match position {
+                Some(position) => values.push(entries.remove(position).1),
+                None => return Err(format!("missing choice value {}", label)),
+            }
+        }
+        match entries.first() {
+            Some((label, _)) => Err(format!("unexpected choice value {}", label)),
+            None => Ok(Self { type_, values }),
+        }
+    }
+}
+
+impl Named {
+    fn new(name: Option<&str>, raw: u32) -> Self {
+        match name {
+            Some(name) => Named::Name(name.to_string()),
+            None => Named::Raw(raw),
+        }
+    }
+
+    fn raw(&self, from_name: impl FnOnce(&str) -> Option<u32>) -> PodResult<u32> {
+        match self {
+            Named::Raw(raw) => Ok(*raw),
+            Named::Name(name) => from_name(name).ok_or_else(|| PodError::UnknownName(name.clone())),
+        }
+    }
+}
+
+/// `Audio:` like prefix of the media specific format keys
+fn media_prefix(media_type: u32) -> Option<String> {
+    let name = MediaType::from_raw(media_type).type_info_name()?;
+    let mut chars = names::short_name(&name).chars();
+    let first = chars.next()?;
+    Some(format!("{}{}:", first.to_uppercase(), chars.as_str()))
+}
+
+/// Property key names after the object type name, like `Audio:rate`
+fn relative_key_names(object_type: Type) -> Vec<(u32, String)> {
+    let Some(type_name) = names::type_name(object_type) else {
+        return Vec::new();
+    };
+    prop_key_names(object_type)
+        .iter()
+        .filter_map(|(key, _)| {
+            let name = names::key_name(object_type, *key)?;
+            Some((*key, name[type_name.len() + 1..].to_string()))
+        })
+        .collect()
+}
+
+/// Short key name, like `rate`, if it is unique for the object media type,
+/// the name after the object type name, like `Video:format`, otherwise.
+fn key_name(object_type: Type, key: u32, media: Option<&str>) -> String {
+    let keys = relative_key_names(object_type);
+    match keys.iter().find(|(k, _)| *k == key) {
+        Some((_, name))
+            if key_from_name(object_type, names::short_name(name), media) == Some(key) =>
+        {
+            names::short_name(name).to_string()
+        }
+        Some((_, name)) => name.clone(),
+        None => key.to_string(),
+    }
+}
+
+fn key_from_name(object_type: Type, name: &str, media: Option<&str>) -> Option<u32> {
+    if let Ok(key) = name.parse() {
+        return Some(key);
+    }
+    let keys = relative_key_names(object_type);
+    if let Some((key, _)) = keys.iter().find(|(_, n)| n == name) {
+        return Some(*key);
+    }
+    let matches: Vec<&(u32, String)> = keys
+        .iter()
+        .filter(|(_, n)| names::short_name(n) == name)
+        .collect();
+    match matches.as_slice() {
+        [(key, _)] => Some(*key),
+        matches => matches
+            .iter()
+            .find(|(_, n)| media.is_some_and(|media| n.starts_with(media)))
+            .map(|(key, _)| *key),
+    }
+}
+
+/// Short name of the id value, if it is parsed back to the same id
+fn id_name(object_type: Type, key: u32, id: u32) -> Option<String> {
+    let name = prop_id_value_name(object_type, key, id)?;
+    let name = names::short_name(&name);
+    (prop_id_value_from_name(object_type, key, name) == Some(id)).then(|| name.to_string())
+}
+
+/// Pod content after the header
+fn body(pod: &PodRef) -> &[u8] {
+    unsafe {
+        slice::from_raw_parts(
+            (pod.as_raw_ptr() as *const u8).add(size_of::<spa_sys::spa_pod>()),
+            pod.size() as usize,
+        )
+    }
+}
+
+impl PodData {
+    fn from_pod(pod: &PodRef) -> PodResult<Self> {
+        Ok(match pod.downcast()? {
+            BasicType::NONE => PodData::None,
+            BasicType::BOOL(pod) => PodData::Bool(pod.value()?),
+            BasicType::ID(pod) => PodData::Id(pod.value()?),
+            BasicType::INT(pod) => PodData::Int(pod.value()?),
+            BasicType::LONG(pod) => PodData::Long(pod.value()?),
+            BasicType::FLOAT(pod) => PodData::Float(pod.value()?),
+            BasicType::DOUBLE(pod) => PodData::Double(pod.value()?),
+            BasicType::STRING(pod) => PodData::String(pod.value()?.to_string_lossy().into()),
+            BasicType::BYTES(pod) => PodData::Bytes(pod.value()?.to_vec()),
+            BasicType::RECTANGLE(pod) => {
+                let value = pod.value()?;
+                PodData::Rectangle {
+                    width: value.width(),
+                    height: value.height(),
+                }
+            }
+            BasicType::FRACTION(pod) => {
+                let value = pod.value()?;
+                PodData::Fraction {
+                    num: value.num(),
+                    denom: value.denom(),
+                }
+            }
+            BasicType::BITMAP(pod) => PodData::Bitmap(pod.value()?.to_vec()),
+            BasicType::ARRAY(_) => {
+                let body = body(pod);
+                let child_size = read_u32(body, 0)? as usize;
+                let child_type = Type::from_raw(read_u32(body, 4)?);
+                let values = if child_size > 0 {
+                    body[size_of::<spa_sys::spa_pod>()..]
+                        .chunks_exact(child_size)
+                        .map(|value| Self::from_raw_value(child_type, value))
+                        .collect::<PodResult<Vec<_>>>()?
+                } else {
+                    Vec::new()
+                };
+                PodData::Array(ArrayData {
+                    child_type: Named::new(names::type_name(child_type), child_type.raw),
+                    values,
+                })
+            }
+            BasicType::STRUCT(pod) => PodData::Struct(
+                pod.value()?
+                    .map(Self::from_pod)
+                    .collect::<PodResult<Vec<_>>>()?,
+            ),
+            BasicType::OBJECT(pod) => {
+                let type_ = pod.body_type();
+                let mut props = Vec::new();
+                // Key type is used only to parse the values
+                for prop in pod.props::<ObjectPropType>() {
+                    let value = Self::from_pod(prop.pod())?;
+                    props.push((prop.key(), prop.flags().bits(), value));
+                }
+                let media = props
+                    .iter()
+                    .find_map(|(key, _, value)| match value {
+                        PodData::Id(id) if Self::is_media_type(type_, *key) => Some(*id),
+                        _ => None,
+                    })
+                    .and_then(media_prefix);
+                let mut properties = Vec::new();
+                let mut flags = Vec::new();
+                for (key, prop_flags, value) in props {
+                    let name = key_name(type_, key, media.as_deref());
+                    if prop_flags != 0 {
+                        flags.push((name.clone(), prop_flags));
+                    }
+                    properties.push((name, value.named_ids(type_, key)));
+                }
+                let id = pod.body_id();
+                PodData::Object(ObjectData {
+                    type_: Named::new(names::type_name(type_), type_.raw),
+                    id: Named::new(names::object_id_name(type_, id).map(names::short_name), id),
+                    properties: Entries(properties),
+                    flags: Entries(flags),
+                })
+            }
+            BasicType::SEQUENCE(_) => {
+                let body = body(pod);
+                let mut controls = Vec::new();
+                let mut offset = size_of::<spa_sys::spa_pod_sequence_body>();
+                while offset < body.len() {
+                    let control_offset = read_u32(body, offset)?;
+                    let type_ = read_u32(body, offset + 4)?;
+                    let (_, _, next) = read_pod(body, offset + 8)?;
+                    // The whole pod is checked to fit into the body
+                    let value = unsafe { PodRef::from_raw_ptr(body[offset + 8..].as_ptr().cast()) };
+                    let type_name = names::control_type_name(control::Type::from_raw(type_));
+                    controls.push(ControlData {
+                        offset: control_offset,
+                        type_: Named::new(type_name.map(names::short_name), type_),
+                        value: Self::from_pod(value)?,
+                    });
+                    offset = next;
+                }
+                PodData::Sequence(SequenceData {
+                    unit: read_u32(body, 0)?,
+                    controls,
+                })
+            }
+            BasicType::POINTER(_) => {
+                let body = body(pod);
+                let type_ = Type::from_raw(read_u32(body, 0)?);
+                PodData::Pointer(PointerData {
+                    type_: Named::new(names::type_name(type_), type_.raw),
+                    value: usize::from_ne_bytes(read(body, 8)?),
+                })
+            }
+            BasicType::FD(_) => PodData::Fd(i64::from_ne_bytes(read(body(pod), 0)?)),
+            BasicType::CHOICE(_) => {
+                let body = body(pod);
+                let choice_type = ChoiceType::from_raw(read_u32(body, 0)?);
+                let child_size = read_u32(body, 8)? as usize;
+                let child_type = Type::from_raw(read_u32(body, 12)?);
+                if child_size == 0 {
+                    return Err(PodError::UnexpectedChoiceElementSize(1, 0));
+                }
+                let mut values = body[size_of::<spa_sys::spa_pod_choice_body>()..]
+                    .chunks_exact(child_size)
+                    .map(|value| Self::from_raw_value(child_type, value))
+                    .collect::<PodResult<Vec<_>>>()?;
+                // The values after the required ones are ignored by SPA
+                let count = match choice_type {
+                    ChoiceType::NONE => 1,
+                    ChoiceType::RANGE => 3,
+                    ChoiceType::STEP => 4,
+                    ChoiceType::ENUM | ChoiceType::FLAGS => values.len().max(1),
+                    _ => return Err(PodError::UnexpectedChoiceElement),
+                };
+                if values.len() < count {
+                    return Err(PodError::ChoiceElementMissing);
+                }
+                values.truncate(count);
+                PodData::Choice(ChoiceData {
+                    type_: choice_type,
+                    values,
+                })
+            }
+            BasicType::POD(_) => return Err(PodError::UnknownPodTypeToDowncast),
+        })
+    }
+
+    /// Parse the array or choice element
+    fn from_raw_value(type_: Type, value: &[u8]) -> PodResult<Self> {
+        Ok(match type_ {
+            Type::BOOL => PodData::Bool(i32::from_ne_bytes(read(value, 0)?) != 0),
+            Type::ID => PodData::Id(read_u32(value, 0)?),
+            Type::INT => PodData::Int(i32::from_ne_bytes(read(value, 0)?)),
+            Type::LONG => PodData::Long(i64::from_ne_bytes(read(value, 0)?)),
+            Type::FLOAT => PodData::Float(f32::from_ne_bytes(read(value, 0)?)),
+            Type::DOUBLE => PodData::Double(f64::from_ne_bytes(read(value, 0)?)),
+            Type::RECTANGLE => PodData::Rectangle {
+                width: read_u32(value, 0)?,
+                height: read_u32(value, 4)?,
+            },
+            Type::FRACTION => PodData::Fraction {
+                num: read_u32(value, 0)?,
+                denom: read_u32(value, 4)?,
+            },
+            Type::FD => PodData::Fd(i64::from_ne_bytes(read(value, 0)?)),
+            _ => return Err(PodError::UnsupportedChoiceElementType),
+        })
+    }
+
+    fn is_media_type(object_type: Type, key: u32) -> bool {
+        object_type == Type::OBJECT_FORMAT && key == Format::MEDIA_TYPE.raw
+    }
+
+    /// Ids of the property as the short names, like `audio`, the strings that look like
+    /// the id names are tagged.
+    fn named_ids(self, object_type: Type, key: u32) -> Self {
+        match self {
+            PodData::Id(id) => match id_name(object_type, key, id) {
+                Some(name) => PodData::String(name),
+                None => PodData::Id(id),
+            },
+            PodData::String(value)
+                if prop_id_value_from_name(object_type, key, &value).is_some() =>
+            {
+                PodData::TaggedString(value)
+            }
+            PodData::Array(mut array) => {
+                array.values = array
+                    .values
+                    .into_iter()
+                    .map(|value| value.named_ids(object_type, key))
+                    .collect();
+                PodData::Array(array)
+            }
+            PodData::Choice(mut choice) => {
+                choice.values = choice
+                    .values
+                    .into_iter()
+                    .map(|value| value.named_ids(object_type, key))
+                    .collect();
+                PodData::Choice(choice)
+            }
+            value => value,
+        }
+    }
+
+    /// Reverse of the [PodData::named_ids]
+    fn ids(self, object_type: Type, key: u32) -> Self {
+        match self {
+            PodData::String(name) => match prop_id_value_from_name(object_type, key, &name) {
+                Some(id) => PodData::Id(id),
+                None => PodData::String(name),
+            },
+            PodData::Array(mut array) => {
+                array.values = array
+                    .values
+                    .into_iter()
+                    .map(|value| value.ids(object_type, key))
+                    .collect();
+                PodData::Array(array)
+            }
+            PodData::Choice(mut choice) => {
+                choice.values = choice
+                    .values
+                    .into_iter()
+                    .map(|value| value.ids(object_type, key))
+                    .collect();
+                PodData::Choice(choice)
+            }
+            value => value,
+        }
+    }
+
+    fn type_(&self) -> Type {
+        match self {
+            PodData::None => Type::NONE,
+            PodData::Bool(_) => Type::BOOL,
+            PodData::Id(_) => Type::ID,
+            PodData::Int(_) => Type::INT,
+            PodData::Long(_) => Type::LONG,
+            PodData::Float(_) => Type::FLOAT,
+            PodData::Double(_) => Type::DOUBLE,
+            PodData::String(_) | PodData::TaggedString(_) => Type::STRING,
+            PodData::Bytes(_) => Type::BYTES,
+            PodData::Rectangle { .. } => Type::RECTANGLE,
+            PodData::Fraction { .. } => Type::FRACTION,
+            PodData::Bitmap(_) => Type::BITMAP,
+            PodData::Array(_) => Type::ARRAY,
+            PodData::Struct(_) => Type::STRUCT,
+            PodData::Object(_) => Type::OBJECT,
+            PodData::Sequence(_) => Type::SEQUENCE,
+            PodData::Pointer(_) => Type::POINTER,
+            PodData::Fd(_) => Type::FD,
+            PodData::Choice(_) => Type::CHOICE,
+        }
+    }
+
+    fn write_pod<W>(&self, buffer: &mut W) -> PodResult<()>
+    where
+        W: Write + Seek,
+    {
+        let mut body = Cursor::new(Vec::new());
+        self.write_body(&mut body)?;
+        let body = body.into_inner();
+        write_header(buffer, body.len() as u32, self.type_())?;
+        buffer.write_all(body.as_slice())?;
+        write_align_padding(buffer)
+    }
+
+    fn write_body<W>(&self, buffer: &mut W) -> PodResult<()>
+    where
+        W: Write + Seek,
+    {
+        match self {
+            PodData::None => Ok(()),
+            PodData::Bool(value) => write_value(buffer, &(*value as i32)),
+            PodData::Id(value) => write_value(buffer, value),
+            PodData::Int(value) => write_value(buffer, value),
+            PodData::Long(value) => write_value(buffer, value),
+            PodData::Float(value) => write_value(buffer, value),
+            PodData::Double(value) => write_value(buffer, value),
+            PodData::String(value) | PodData::TaggedString(value) => {
+                buffer.write_all(value.as_bytes())?;
+                Ok(buffer.write_all(&[0])?)
+            }
+            PodData::Bytes(value) | PodData::Bitmap(value) => Ok(buffer.write_all(value)?),
+            PodData::Rectangle { width, height } => {
+                write_value(buffer, width)?;
+                write_value(buffer, height)
+            }
+            PodData::Fraction { num, denom } => {
+                write_value(buffer, num)?;
+                write_value(buffer, denom)
+            }
+            PodData::Array(array) => {
+                let child_type = Type::from_raw(
+                    array
+                        .child_type
+                        .raw(|name| names::type_from_name(name).map(|type_| type_.raw))?,
+                );
+                let values = Self::write_values(child_type, array.values.iter())?;
+                write_header(buffer, values.child_size, child_type)?;
+                Ok(buffer.write_all(values.content.as_slice())?)
+            }
+            PodData::Struct(fields) => {
+                for field in fields {
+                    field.write_pod(buffer)?;
+                }
+                Ok(())
+            }
+            PodData::Object(object) => {
+                let type_ = Type::from_raw(
+                    object
+                        .type_
+                        .raw(|name| names::type_from_name(name).map(|type_| type_.raw))?,
+                );
+                let id = object.id.raw(|name| {
+                    names::param_type_from_name(name).map(|param_type| param_type.raw)
+                })?;
+                write_value(buffer, &type_.raw)?;
+                write_value(buffer, &id)?;
+                let media = object
+                    .properties
+                    .0
+                    .iter()
+                    .find_map(|(name, value)| {
+                        let key = key_from_name(type_, name, None)?;
+                        match value.clone().ids(type_, key) {
+                            PodData::Id(id) if Self::is_media_type(type_, key) => Some(id),
+                            _ => None,
+                        }
+                    })
+                    .and_then(media_prefix);
+                for (name, value) in object.properties.0.iter() {
+                    let key = key_from_name(type_, name, media.as_deref())
+                        .ok_or_else(|| PodError::UnknownName(name.clone()))?;
+                    let prop_flags = object
+                        .flags
+                        .0
+                        .iter()
+                        .find(|(n, _)| n == name)
+                        .map(|(_, flags)| *flags)
+                        .unwrap_or_default();
+                    write_value(buffer, &key)?;
+                    write_value(buffer, &prop_flags)?;
+                    value.clone().ids(type_, key).write_pod(buffer)?;
+                }
+                Ok(())
+            }
+            PodData::Sequence(sequence) => {
+                write_value(buffer, &sequence.unit)?;
+                write_value(buffer, &0u32)?;
+                for control in &sequence.controls {
+                    let type_ = control
+                        .type_
+                        .raw(|name| names::control_type_from_name(name).map(|type_| type_.raw))?;
+                    write_value(buffer, &control.offset)?;
+                    write_value(buffer, &type_)?;
+                    control.value.write_pod(buffer)?;
+                }
+                Ok(())
+            }
+            PodData::Pointer(pointer) => {
+                let type_ = pointer
+                    .type_
+                    .raw(|name| names::type_from_name(name).map(|type_| type_.raw))?;
+                write_value(buffer, &type_)?;
+                write_value(buffer, &0u32)?;
+                write_value(buffer, &pointer.value)
+            }
+            PodData::Fd(value) => write_value(buffer, value),
+            PodData::Choice(choice) => {
+                let child_type = choice
+                    .values
+                    .first()
+                    .ok_or(PodError::ChoiceElementMissing)?
+                    .type_();
+                let values = Self::write_values(child_type, choice.values.iter())?;
+                write_value(buffer, &choice.type_.raw)?;
+                write_value(buffer, &0u32)?;
+                write_header(buffer, values.child_size, child_type)?;
+                Ok(buffer.write_all(values.content.as_slice())?)
+            }
+        }
+    }
+
+    /// Write the array or choice elements, all elements must have the same type and size
+    fn write_values<'a>(
+        child_type: Type,
+        values: impl Iterator<Item = &'a PodData>,
+    ) -> PodResult<RawValues> {
+        let mut content = Cursor::new(Vec::new());
+        let mut child_size = None;
+        for value in values {
+            if value.type_() != child_type {
+                return Err(PodError::WrongPodTypeToCast(child_type, value.type_()));
+            }
+            let start = content.get_ref().len();
+            value.write_body(&mut content)?;
+            let size = content.get_ref().len() - start;
+            match child_size {
+                Some(child_size) if child_size != size => {
+                    return Err(PodError::UnexpectedChoiceElementSize(child_size, size));
+                }
+                _ => child_size = Some(size),
+            }
+        }
+        Ok(RawValues {
+            child_size: child_size.unwrap_or_else(|| raw_value_size(child_type)) as u32,
+            content: content.into_inner(),
+        })
+    }
+}
+
+/// Element size for the empty arrays
+fn raw_value_size(type_: Type) -> usize {
+    match type_ {
+        Type::BOOL | Type::ID | Type::INT | Type::FLOAT => 4,
+        Type::LONG | Type::DOUBLE | Type::FD | Type::RECTANGLE | Type::FRACTION => 8,
+        Type::POINTER => size_of::<spa_sys::spa_pod_pointer_body>(),
+        _ => 0,
+    }
+}
+
+struct RawValues {
+    child_size: u32,
+    content: Vec<u8>,
+}
+
+impl Serialize for PodRef {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        PodData::from_pod(self)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for AllocPod<PodRef> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = PodData::deserialize(deserializer)?;
+        let mut buf = PodBuf::<PodRef>::new();
+        data.write_pod(&mut buf).map_err(D::Error::custom)?;
+        Ok(buf.into_pod())
+    }
+}
+
+impl<T> Serialize for AllocPod<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_pod().serialize(serializer)
+    }
+}
+
+macro_rules! serialize_as_pod_ref {
+    // Generic types first, the types with bounds can be parsed as `ty` too
+    ($($pod_type:ident<T: $bound:path>),* $(,)?) => {$(
+        impl<T: $bound> Serialize for $pod_type<T>
+        where
+            Self: BasicTypePod,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.upcast().serialize(serializer)
+            }
+        }
+    )*};
+    ($($pod_type:ty),* $(,)?) => {$(
+        impl Serialize for $pod_type {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.upcast().serialize(serializer)
+            }
+        }
+    )*};
+}
+
+serialize_as_pod_ref!(
+    PodBoolRef,
+    PodIntRef,
+    PodLongRef,
+    PodFloatRef,
+    PodDoubleRef,
+    PodStringRef,
+    PodBytesRef,
+    PodRectangleRef,
+    PodFractionRef,
+    PodBitmapRef,
+    PodStructRef,
+    PodObjectRef,
+    PodSequenceRef,
+    PodPointerRef,
+    PodFdRef,
+);
+
+serialize_as_pod_ref!(
+    PodIdRef<T: PodIdType>,
+    PodArrayRef<T: PodRawValue>,
+    PodChoiceRef<T: PodRawValue>,
+    PodNoneRef<T: PodRawValue>,
+    PodRangeRef<T: PodRawValue>,
+    PodStepRef<T: PodRawValue>,
+    PodEnumRef<T: PodRawValue>,
+    PodFlagsRef<T: PodRawValue>,
+);
+
+#[test]
+fn test_object_round_trip() {
+    let enum_format = audio_enum_format_builder()
+        .audio_format(ChoiceStructType::ENUM(PodEnumValue::new(
+            AudioFormat::F32_LE,
+            vec![AudioFormat::F32_LE, AudioFormat::S16_LE],
+        )))
+        .audio_rate(ChoiceStructType::RANGE(PodRangeValue::new(
+            44100, 8000, 96000,
+        )))
+        .build()
+        .unwrap();
+
+    let json = serde_json::to_value(&enum_format).unwrap();
+    let properties = &json["Object"]["properties"];
+    assert_eq!(json["Object"]["type"], "Spa:Pod:Object:Param:Format");
+    assert_eq!(json["Object"]["id"], "EnumFormat");
+    assert_eq!(properties["mediaType"], "audio");
+    assert_eq!(properties["mediaSubtype"], "raw");
+    assert_eq!(
+        properties["format"],
+        serde_json::json!({ "default": "F32LE", "alt1": "F32LE", "alt2": "S16LE" })
+    );
+    assert_eq!(
+        properties["rate"],
+        serde_json::json!({ "default": 44100, "min": 8000, "max": 96000 })
+    );
+
+    let deserialized: AllocPod<PodRef> = serde_json::from_value(json).unwrap();
+    let original = enum_format.as_pod().upcast();
+    assert_eq!(deserialized.as_pod().type_(), original.type_());
+    assert_eq!(body(deserialized.as_pod()), body(original));
+}
+
+#[test]
+fn test_struct_round_trip() {
+    let json = serde_json::json!([
+        null,
+        true,
+        1,
+        1.5,
+        "string",
+        { "String": "tagged" },
+        { "Id": 3 },
+        { "Long": 3_000_000_000i64 },
+        { "Float": "NaN" },
+        { "Double": "-inf" },
+        { "width": 640, "height": 480 },
+        { "num": 30, "denom": 1 },
+        { "Array": { "type": "Spa:Int", "values": [1, 2, 3] } },
+        { "default": 2, "min": 0, "max": 10, "step": 2 },
+        { "type": "Enum", "default": 1 },
+        { "default": 1 },
+        { "Sequence": { "unit": 0, "controls": [{ "offset": 1, "type": "Properties", "value": [] }] } },
+        { "Object": { "type": "Spa:Pod:Object:Param:Props", "id": "Props", "properties": { "volume": 0.5 } } }
+    ]);
+
+    let pod: AllocPod<PodRef> = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(pod.as_pod().type_(), Type::STRUCT);
+    assert_eq!(serde_json::to_value(&pod).unwrap(), json);
+
+    // Plain integers are always Int, the larger values must be tagged as Long
+    let out_of_range = serde_json::json!([3_000_000_000i64]);
+    assert!(serde_json::from_value::<AllocPod<PodRef>>(out_of_range).is_err());
+
+    let unknown_key = serde_json::json!({
+        "Object": { "type": "Spa:Pod:Object:Param:Props", "id": 2, "properties": { "unknown": 1 } }
+    });
+    assert!(serde_json::from_value::<AllocPod<PodRef>>(unknown_key).is_err());
+}
@@ -0,0 +1,351 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Pod tree validation for the untrusted data.
+//!
+use std::mem::size_of;
+
+use crate::spa::pod::choice::ChoiceType;
+use crate::spa::pod::restricted::{read_pod, read_u32};
+use crate::spa::pod::{PodError, PodRef, PodResult, POD_ALIGN};
+use crate::spa::type_::Type;
+use crate::wrapper::RawWrapper;
+
+#[cfg(test)]
+use crate::spa::pod::{
+    choice::{enum_::PodEnumValue, range::PodRangeValue, ChoiceStructType},
+    object::audio_enum_format_builder,
+    Upcast,
+};
+
+/// Max depth of the nested pods, deeper pods are rejected to keep the stack bounded.
+const MAX_DEPTH: usize = 64;
+
+impl PodRef {
+    /// Pod from the untrusted bytes.
+    ///
+    /// The whole pod tree is checked before any typed access: the slice must be aligned,
+    /// every header and body must fit into the parent, array and choice elements must fit
+    /// into the body and must have exactly the size of the child type. Elements of the
+    /// container types are rejected.
+    pub fn from_bytes(bytes: &[u8]) -> PodResult<&PodRef> {
+        if bytes.as_ptr().align_offset(POD_ALIGN) != 0 {
+            return Err(PodError::PodIsNotAligned);
+        }
+        let (type_, body, _) = read_pod(bytes, 0)?;
+        validate_body(type_, body, 0)?;
+        Ok(unsafe { PodRef::from_raw_ptr(bytes.as_ptr().cast()) })
+    }
+}
+
+fn check_size(body: &[u8], size: usize) -> PodResult<()> {
+    if body.len() < size {
+        Err(PodError::DataIsTooShort(size, body.len()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Size of the values with the fixed layout
+fn value_size(type_: Type) -> Option<usize> {
+    match type_ {
+        Type::BOOL | Type::ID | Type::INT | Type::FLOAT => Some(size_of::<u32>()),
+        Type::LONG | Type::DOUBLE | Type::FD => Some(size_of::<u64>()),
+        Type::RECTANGLE => Some(size_of::<spa_sys::spa_rectangle>()),
+        Type::FRACTION => Some(size_of::<spa_sys::spa_fraction>()),
+        Type::POINTER => Some(size_of::<spa_sys::spa_pod_pointer_body>()),
+        _ => None,
+    }
+}
+
+fn validate_body(type_: Type, body: &[u8], depth: usize) -> PodResult<()> {
+    if depth > MAX_DEPTH {
+        return Err(PodError::PodIsTooDeep);
+    }
+    if let Some(size) = value_size(type_) {
+        return check_size(body, size);
+    }
+    match type_ {
+        Type::STRING => match body.last() {
+            Some(0) => Ok(()),
+            _ => Err(PodError::StringIsNotNullTerminated),
+        },
+        Type::ARRAY => {
+            let (child_size, child_type) = read_child(body, 0)?;
+            let values = &body[size_of::<spa_sys::spa_pod>()..];
+            validate_elements(child_type, child_size, values, depth)?;
+            Ok(())
+        }
+        Type::CHOICE => {
+            check_size(body, size_of::<spa_sys::spa_pod_choice_body>())?;
+            let choice_type = ChoiceType::from_raw(read_u32(body, 0)?);
+            let (child_size, child_type) = read_child(body, 8)?;
+            let values = &body[size_of::<spa_sys::spa_pod_choice_body>()..];
+            let count = validate_elements(child_type, child_size, values, depth)?;
+            let required = match choice_type {
+                ChoiceType::RANGE => 3,
+                ChoiceType::STEP => 4,
+                _ => 1,
+            };
+            if count < required {
+                return Err(PodError::ChoiceElementMissing);
+            }
+            Ok(())
+        }
+        Type::STRUCT => {
+            let mut offset = 0;
+            while offset < body.len() {
+                let (field_type, field, next) = read_pod(body, offset)?;
+                validate_body(field_type, field, depth + 1)?;
+                offset = next;
+            }
+            Ok(())
+        }
+        Type::OBJECT => {
+            // Props have the key and flags before the value pod
+            validate_entries(body, size_of::<spa_sys::spa_pod_object_body>(), depth)
+        }
+        Type::SEQUENCE => {
+            // Controls have the offset and type before the value pod
+            validate_entries(body, size_of::<spa_sys::spa_pod_sequence_body>(), depth)
+        }
+        // None, Bytes, Bitmap and unknown types have no structure to check
+        _ => Ok(()),
+    }
+}
+
+fn read_child(body: &[u8], offset: usize) -> PodResult<(usize, Type)> {
+    let child_size = read_u32(body, offset)? as usize;
+    let child_type = Type::from_raw(read_u32(body, offset + 4)?);
+    Ok((child_size, child_type))
+}
+
+/// Validate the array or choice elements, returns the elements count
+fn validate_elements(
+    child_type: Type,
+    child_size: usize,
+    values: &[u8],
+    depth: usize,
+) -> PodResult<usize> {
+    match child_type {
+        // Elements are stored without the headers, the nested pods can't be read in place
+        Type::STRUCT | Type::OBJECT | Type::SEQUENCE | Type::ARRAY | Type::CHOICE | Type::POD => {
+            return Err(PodError::UnsupportedChoiceElementType)
+        }
+        _ => {}
+    }
+    // Larger elements would break the alignment of the next ones
+    match value_size(child_type) {
+        Some(size) if size != child_size => {
+            return Err(PodError::UnexpectedChoiceElementSize(size, child_size))
+        }
+        _ => {}
+    }
+    if values.is_empty() {
+        return Ok(0);
+    }
+    if child_size == 0 || values.len() % child_size != 0 {
+        return Err(PodError::UnexpectedChoiceElementSize(
+            child_size,
+            values.len(),
+        ));
+    }
+    for value in values.chunks_exact(child_size) {
+        validate_body(child_type, value, depth + 1)?;
+    }
+    Ok(values.len() / child_size)
+}
+
+/// Validate the object props or sequence controls, both have 8 bytes before the pod
+fn validate_entries(body: &[u8], header_size: usize, depth: usize) -> PodResult<()> {
+    check_size(body, header_size)?;
+    let mut offset = header_size;
+    while offset < body.len() {
+        let (value_type, value, next) = read_pod(body, offset + 8)?;
+        validate_body(value_type, value, depth + 1)?;
+        offset = next;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+struct Random(u64);
+
+#[cfg(test)]
+impl Random {
+    /// Xorshift generator, the tests must be reproducible
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, max: usize) -> usize {
+        (self.next() % max as u64) as usize
+    }
+}
+
+/// Aligned buffer with the given bytes
+#[cfg(test)]
+fn aligned(bytes: &[u8]) -> Vec<u64> {
+    let mut buffer = vec![0u64; bytes.len().div_ceil(size_of::<u64>())];
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), buffer.as_mut_ptr().cast(), bytes.len());
+    }
+    buffer
+}
+
+#[cfg(test)]
+fn as_bytes(buffer: &[u64], len: usize) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(buffer.as_ptr().cast(), len) }
+}
+
+/// Parse the bytes and access every value of the valid pods
+#[cfg(test)]
+fn parse_and_visit(bytes: &[u8]) -> bool {
+    let buffer = aligned(bytes);
+    match PodRef::from_bytes(as_bytes(&buffer, bytes.len())) {
+        Ok(pod) => {
+            assert!(!format!("{:?}", pod).is_empty());
+            assert!(!pod.debug().to_string().is_empty());
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+fn valid_pod_bytes() -> Vec<u8> {
+    let pod = audio_enum_format_builder()
+        .audio_rate(ChoiceStructType::RANGE(PodRangeValue::new(
+            44100, 8000, 96000,
+        )))
+        .audio_channels(ChoiceStructType::ENUM(PodEnumValue::new(2, vec![1, 2])))
+        .build()
+        .unwrap();
+    let pod = pod.as_pod().upcast();
+    let len = pod.size() as usize + size_of::<spa_sys::spa_pod>();
+    unsafe { std::slice::from_raw_parts(pod.as_raw_ptr() as *const u8, len).to_vec() }
+}
+
+#[test]
+fn test_from_bytes() {
+    let bytes = valid_pod_bytes();
+    let buffer = aligned(&bytes);
+    let pod = PodRef::from_bytes(as_bytes(&buffer, bytes.len())).unwrap();
+    assert_eq!(pod.type_(), Type::OBJECT);
+    assert!(parse_and_visit(&bytes));
+
+    // Every truncated pod must be rejected
+    for len in 0..bytes.len() {
+        assert!(PodRef::from_bytes(as_bytes(&buffer, len)).is_err());
+    }
+
+    let unaligned = &as_bytes(&buffer, bytes.len())[4..];
+    assert!(matches!(
+        PodRef::from_bytes(unaligned),
+        Err(PodError::PodIsNotAligned)
+    ));
+}
+
+#[test]
+fn test_from_bytes_nested_too_deep() {
+    let depth = MAX_DEPTH + 2;
+    let mut bytes = Vec::new();
+    for i in 0..depth {
+        let size = ((depth - i - 1) * size_of::<spa_sys::spa_pod>()) as u32;
+        bytes.extend_from_slice(&size.to_ne_bytes());
+        bytes.extend_from_slice(&Type::STRUCT.raw.to_ne_bytes());
+    }
+    let buffer = aligned(&bytes);
+    assert!(matches!(
+        PodRef::from_bytes(as_bytes(&buffer, bytes.len())),
+        Err(PodError::PodIsTooDeep)
+    ));
+}
+
+#[test]
+fn test_from_bytes_random() {
+    let mut random = Random(0x2545_f491_4f6c_dd1d);
+    for _ in 0..10000 {
+        let len = random.below(256);
+        let mut bytes: Vec<u8> = (0..len).map(|_| random.next() as u8).collect();
+        // Plausible headers make the deeper checks reachable
+        if len >= 8 {
+            let size = random.below(len - 7) as u32;
+            let type_ = 1 + random.below(Type::POD.raw as usize) as u32;
+            bytes[0..4].copy_from_slice(&size.to_ne_bytes());
+            bytes[4..8].copy_from_slice(&type_.to_ne_bytes());
+        }
+        parse_and_visit(&bytes);
+    }
+}
+
+#[test]
+fn test_from_bytes_mutated() {
+    let mut random = Random(0x9e37_79b9_7f4a_7c15);
+    let valid = valid_pod_bytes();
+    for _ in 0..10000 {
+        let mut bytes = valid.clone();
+        for _ in 0..1 + random.below(4) {
+            let index = random.below(bytes.len());
+            bytes[index] = random.next() as u8;
+        }
+        parse_and_visit(&bytes);
+    }
+}
+
+/// Array pod with the given child header and the random elements
+#[cfg(test)]
+fn array_bytes(random: &mut Random, child_type: Type, child_size: usize, count: usize) -> Vec<u8> {
+    let size = size_of::<spa_sys::spa_pod>() + child_size * count;
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&(size as u32).to_ne_bytes());
+    bytes.extend_from_slice(&Type::ARRAY.raw.to_ne_bytes());
+    bytes.extend_from_slice(&(child_size as u32).to_ne_bytes());
+    bytes.extend_from_slice(&child_type.raw.to_ne_bytes());
+    bytes.extend((0..child_size * count).map(|_| random.next() as u8));
+    bytes
+}
+
+#[test]
+fn test_from_bytes_elements() {
+    let mut random = Random(0x5851_f42d_4c95_7f2d);
+    let bytes = array_bytes(&mut random, Type::LONG, size_of::<u64>(), 3);
+    assert!(parse_and_visit(&bytes));
+
+    let bytes = array_bytes(&mut random, Type::LONG, 12, 2);
+    let buffer = aligned(&bytes);
+    assert!(matches!(
+        PodRef::from_bytes(as_bytes(&buffer, bytes.len())),
+        Err(PodError::UnexpectedChoiceElementSize(8, 12))
+    ));
+
+    let bytes = array_bytes(&mut random, Type::STRUCT, 16, 2);
+    let buffer = aligned(&bytes);
+    assert!(matches!(
+        PodRef::from_bytes(as_bytes(&buffer, bytes.len())),
+        Err(PodError::UnsupportedChoiceElementType)
+    ));
+}
+
+#[test]
+fn test_from_bytes_random_elements() {
+    let mut random = Random(0xd1b5_4a32_d192_ed03);
+    for _ in 0..10000 {
+        let child_type = Type::from_raw(1 + random.below(Type::POD.raw as usize) as u32);
+        let child_size = random.below(24);
+        let count = random.below(4);
+        let bytes = array_bytes(&mut random, child_type, child_size, count);
+        if parse_and_visit(&bytes) {
+            // Only the elements that keep the alignment are accepted
+            assert!(!matches!(value_size(child_type), Some(size) if size != child_size));
+            assert!(!matches!(
+                child_type,
+                Type::STRUCT | Type::OBJECT | Type::SEQUENCE | Type::ARRAY | Type::CHOICE
+            ));
+        }
+    }
+}
@@ -6,18 +6,25 @@
 //!
 use std::ffi::{CStr, CString};
 use std::pin::Pin;
-use std::ptr::{null_mut, NonNull};
+use std::ptr::{null, null_mut, NonNull};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
 use bitflags::{bitflags, Flags};
 use spa_sys::spa_pod;
 
 use pipewire_wrapper_proc_macro::{RawWrapper, Wrapper};
 
-use crate::core_api::core::CoreRef;
-use crate::core_api::properties::{Properties, PropertiesRef};
-use crate::core_api::PW_ID_ANY;
+use crate::core_api::core::{Core, CoreRef};
+use crate::core_api::node::NodeRef;
+use crate::core_api::properties::{key, Properties, PropertiesRef};
+use crate::core_api::proxy::Proxied;
+use crate::core_api::registry::events::RegistryEventsBuilder;
+use crate::core_api::{require_library_version, PW_ID_ANY};
 use crate::listeners::{AddListener, Listeners, OwnListeners};
 use crate::spa::dict::DictRef;
+use crate::spa::param::ParamType;
 use crate::spa::pod::object::param_port_config::Direction;
 use crate::spa::pod::PodRef;
 use crate::stream::buffer::BufferRef;
@@ -28,6 +35,8 @@ use crate::wrapper::{RawWrapper, Wrapper};
 use crate::{enum_wrapper, spa_interface_call};
 use crate::{i32_as_result, i32_as_void_result, new_instance_raw_wrapper, raw_wrapper};
 
+#[cfg(feature = "spa-pod-object-builders")]
+pub mod audio;
 pub mod buffer;
 pub mod control;
 pub mod events;
@@ -58,7 +67,64 @@ bitflags! {
         const DONT_RECONNECT = pw_sys::pw_stream_flags_PW_STREAM_FLAG_DONT_RECONNECT;
         const ALLOC_BUFFERS = pw_sys::pw_stream_flags_PW_STREAM_FLAG_ALLOC_BUFFERS;
         const TRIGGER = pw_sys::pw_stream_flags_PW_STREAM_FLAG_TRIGGER;
-        // since 0.3.73 const ASYNC = pw_sys::pw_stream_flags_PW_STREAM_FLAG_ASYNC;
+        #[cfg(feature = "v0_3_73")]
+        const ASYNC = pw_sys::pw_stream_flags_PW_STREAM_FLAG_ASYNC;
+    }
+}
+
+/// Stream connection target, used by [Stream::connect_to].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StreamTarget {
+    /// Target is selected by the session manager
+    Any,
+    /// Node id
+    NodeId(u32),
+    /// Node `object.serial` property
+    ObjectSerial(u64),
+    /// Node `node.name` property
+    NodeName(CString),
+}
+
+impl StreamTarget {
+    /// Resolve the [StreamTarget::NodeName] target with the registry.
+    /// The node is resolved to [StreamTarget::ObjectSerial] or to [StreamTarget::NodeId]
+    /// if the node has no serial, other targets are returned as is.
+    /// Blocks until the registry globals are received.
+    ///
+    /// Returns error if the node with the given name is not found.
+    pub fn resolve(self, core: &Core) -> crate::Result<Self> {
+        let name = match self {
+            StreamTarget::NodeName(name) => name,
+            target => return Ok(target),
+        };
+        let registry = core.get_registry(0)?;
+        let resolved = Rc::new(Mutex::new(None));
+        let _listener = registry.as_ref().add_listener(
+            RegistryEventsBuilder::default()
+                .global(Box::new({
+                    let resolved = resolved.clone();
+                    move |id, _permissions, type_info, _version, props| {
+                        if type_info == NodeRef::type_info()
+                            && props.get(key(pw_sys::PW_KEY_NODE_NAME)) == Some(name.as_c_str())
+                        {
+                            let serial = props
+                                .get(key(pw_sys::PW_KEY_OBJECT_SERIAL))
+                                .and_then(|serial| serial.to_str().ok())
+                                .and_then(|serial| serial.parse().ok());
+                            *resolved.lock().unwrap() = Some(match serial {
+                                Some(serial) => StreamTarget::ObjectSerial(serial),
+                                None => StreamTarget::NodeId(id),
+                            });
+                        }
+                    }
+                }))
+                .build(),
+        );
+        core.roundtrip()?;
+        let resolved = resolved.lock().unwrap().take();
+        resolved.ok_or(crate::Error::ErrorMessage(
+            "Node with the given name is not found",
+        ))
     }
 }
 
@@ -112,17 +178,32 @@ impl StreamRef {
         unsafe { pw_sys::pw_stream_update_properties(self.as_raw_ptr(), properties.as_raw_ptr()) }
     }
 
+    /// Connect the stream, the target is selected by the session manager.
     pub fn connect(
         &self,
         direction: Direction,
         flags: StreamFlags,
         params: &[&PodRef],
     ) -> crate::Result<()> {
+        self.connect_id(direction, PW_ID_ANY, flags, params)
+    }
+
+    fn connect_id(
+        &self,
+        direction: Direction,
+        target_id: u32,
+        flags: StreamFlags,
+        params: &[&PodRef],
+    ) -> crate::Result<()> {
+        #[cfg(feature = "v0_3_73")]
+        if flags.contains(StreamFlags::ASYNC) {
+            require_library_version(0, 3, 73)?;
+        }
         let result = unsafe {
             pw_sys::pw_stream_connect(
                 self.as_raw_ptr(),
                 direction.raw,
-                PW_ID_ANY,
+                target_id,
                 flags.bits(),
                 params.as_ptr() as *const *const spa_pod as *mut *const spa_pod,
                 params.len() as u32,
@@ -131,6 +212,8 @@ impl StreamRef {
         i32_as_void_result(result)
     }
 
+    /// Id of the stream node.
+    /// Returns [PW_ID_ANY] until the stream is connected.
     pub fn get_node_id(&self) -> u32 {
         unsafe { pw_sys::pw_stream_get_node_id(self.as_raw_ptr()) }
     }
@@ -153,11 +236,15 @@ impl StreamRef {
         i32_as_void_result(result)
     }
 
-    // pub fn set_param(&self, id: u32, param: &PodRef) -> crate::Result<()> {
-    //     let result =
-    //         unsafe { pw_sys::pw_stream_set_param(self.as_raw_ptr(), id, param.as_raw_ptr()) };
-    //     i32_as_void_result(result)
-    // } Since 0.3.70
+    /// Set the stream param, available since PipeWire 0.3.70.
+    #[cfg(feature = "v0_3_70")]
+    pub fn set_param(&self, id: ParamType, param: &PodRef) -> crate::Result<()> {
+        require_library_version(0, 3, 70)?;
+        let result = unsafe {
+            pw_sys::pw_stream_set_param(self.as_raw_ptr(), *id.as_raw(), param.as_raw_ptr())
+        };
+        i32_as_void_result(result)
+    }
 
     pub fn get_control(&self, id: u32) -> Option<&ControlRef> {
         unsafe {
@@ -179,13 +266,27 @@ impl StreamRef {
         i32_as_void_result(result)
     }
 
-    // todo: use get_time_n in new version
+    #[cfg(not(feature = "v0_3_56"))]
     pub fn get_time(&self) -> crate::Result<TimeRef> {
         let time = TimeRef::default();
         let result = unsafe { pw_sys::pw_stream_get_time(self.as_raw_ptr(), time.as_raw_ptr()) };
         i32_as_result(result, time)
     }
 
+    #[cfg(feature = "v0_3_56")]
+    pub fn get_time(&self) -> crate::Result<TimeRef> {
+        require_library_version(0, 3, 50)?;
+        let time = TimeRef::default();
+        let result = unsafe {
+            pw_sys::pw_stream_get_time_n(
+                self.as_raw_ptr(),
+                time.as_raw_ptr(),
+                std::mem::size_of::<pw_sys::pw_time>(),
+            )
+        };
+        i32_as_result(result, time)
+    }
+
     pub fn dequeue_buffer(&self) -> Option<&mut BufferRef> {
         unsafe {
             pw_sys::pw_stream_dequeue_buffer(self.as_raw_ptr())
@@ -243,6 +344,9 @@ pub struct Stream {
     ref_: NonNull<StreamRef>,
 
     listeners: Listeners<Pin<Box<StreamEvents>>>,
+
+    /// The `target.object` property is set by [Stream::connect_to]
+    target_object: AtomicBool,
 }
 
 impl Stream {
@@ -253,10 +357,75 @@ impl Stream {
             Ok(Self {
                 ref_,
                 listeners: Default::default(),
+                target_object: AtomicBool::new(false),
             })
         }
     }
 
+    /// Connect the stream to the given target.
+    /// [StreamTarget::ObjectSerial] and [StreamTarget::NodeName] are set as the `target.object`
+    /// stream property, so the session manager links the stream to the target.
+    /// The property is removed on the next connection only if it was set by this method,
+    /// the `target.object` from the stream properties is kept as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - stream direction
+    /// * `target` - stream target
+    /// * `flags` - stream flags
+    /// * `params` - stream params, f.e. supported formats
+    pub fn connect_to(
+        &self,
+        direction: Direction,
+        target: StreamTarget,
+        flags: StreamFlags,
+        params: &[&PodRef],
+    ) -> crate::Result<()> {
+        let target_id = match target {
+            StreamTarget::Any => {
+                self.reset_target_object();
+                PW_ID_ANY
+            }
+            StreamTarget::NodeId(id) => {
+                self.reset_target_object();
+                id
+            }
+            StreamTarget::ObjectSerial(serial) => {
+                self.set_target_object(Some(&CString::new(serial.to_string()).unwrap()));
+                PW_ID_ANY
+            }
+            StreamTarget::NodeName(name) => {
+                self.set_target_object(Some(&name));
+                PW_ID_ANY
+            }
+        };
+        self.connect_id(direction, target_id, flags, params)
+    }
+
+    /// Remove the `target.object` property if it was set by the previous connection,
+    /// the target from the previous connection must not be reused
+    fn reset_target_object(&self) {
+        if self.target_object.swap(false, Ordering::Relaxed) {
+            self.set_target_object(None);
+        }
+    }
+
+    /// Set or remove the `target.object` stream property
+    fn set_target_object(&self, value: Option<&CStr>) {
+        self.target_object.store(value.is_some(), Ordering::Relaxed);
+        // The item with the null value removes the property
+        let item = spa_sys::spa_dict_item {
+            key: key(pw_sys::PW_KEY_TARGET_OBJECT).as_ptr(),
+            value: value.map_or(null(), CStr::as_ptr),
+        };
+        let dict = spa_sys::spa_dict {
+            flags: 0,
+            n_items: 1,
+            items: &item,
+        };
+        unsafe { pw_sys::pw_stream_update_properties(self.as_raw_ptr(), &dict) };
+    }
+
     // todo new_simple
 }
 
@@ -0,0 +1,497 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+//! High-level audio [Stream] with the format negotiation.
+//!
+//! # Examples
+//!
+//! ```no_run,ignore
+//! let stream = AudioStreamBuilder::<f32>::default()
+//!     .format(ChoiceStructType::NONE(AudioFormat::F32))
+//!     .channels(ChoiceStructType::NONE(2))
+//!     .rate(ChoiceStructType::NONE(48000))
+//!     .latency(256, 48000)
+//!     .on_format(Box::new(|info| println!("Negotiated format {:?}", info)))
+//!     .playback(Box::new(|info, buffer| {
+//!         if let AudioBufferMut::Interleaved(interleaved) = buffer {
+//!             interleaved.samples_mut().fill(0f32);
+//!         }
+//!         buffer.frames()
+//!     }))
+//!     .build(&core, CString::new("Audio source").unwrap().as_ref())?;
+//! ```
+//!
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::rc::Rc;
+
+use crate::core_api::core::CoreRef;
+use crate::core_api::properties::{key, Properties};
+use crate::listeners::OwnListeners;
+use crate::spa::buffers;
+use crate::spa::buffers::audio::{
+    AudioSample, InterleavedBuffer, InterleavedBufferMut, PlanarBuffer, PlanarBufferMut,
+};
+use crate::spa::param::ParamType;
+use crate::spa::pod::choice::range::PodRangeValue;
+use crate::spa::pod::choice::ChoiceStructType;
+use crate::spa::pod::id::PodIdRef;
+use crate::spa::pod::iterator::AllocatedPodValueIterator;
+use crate::spa::pod::object::enum_format::ObjectEnumFormatBuilder;
+use crate::spa::pod::object::format::{AudioFormat, MediaSubType, MediaType, ObjectFormatType};
+use crate::spa::pod::object::param_buffers::ParamBuffersBuilder;
+use crate::spa::pod::object::param_meta::{MetaType, ParamMetaBuilder};
+use crate::spa::pod::object::param_port_config::Direction;
+use crate::spa::pod::object::prop::AudioChannel;
+use crate::spa::pod::object::{ObjectType, PodObjectRef};
+use crate::spa::pod::pod_buf::AllocPod;
+use crate::spa::pod::{BasicType, PodIntRef, PodRef, PodValue, Upcast};
+use crate::stream::events::StreamEventsBuilder;
+use crate::stream::{Stream, StreamFlags, StreamTarget};
+
+const MIN_BUFFERS: i32 = 1;
+const DEFAULT_BUFFERS: i32 = 8;
+const MAX_BUFFERS: i32 = 32;
+
+/// Error code reported to the stream for the errors without the code
+const EINVAL: i32 = 22;
+
+/// Negotiated audio format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AudioInfo {
+    pub format: AudioFormat,
+    pub rate: u32,
+    pub channels: u32,
+    /// Channels positions, can be empty if the positions are not negotiated
+    pub position: Vec<AudioChannel>,
+}
+
+impl AudioInfo {
+    /// Parse the [ParamType::FORMAT] param.
+    ///
+    /// Returns error if the param is not the raw audio format.
+    pub fn from_format_param(param: &PodRef) -> crate::Result<Self> {
+        let object = match param.downcast()? {
+            BasicType::OBJECT(object) => object,
+            _ => return Err(crate::Error::TypeMismatch),
+        };
+        let props = match object.param_value(ParamType::FORMAT)? {
+            ObjectType::OBJECT_FORMAT(props) => props,
+            _ => return Err(crate::Error::TypeMismatch),
+        };
+        let mut format = None;
+        let mut rate = None;
+        let mut channels = None;
+        let mut position = Vec::new();
+        for prop in props {
+            match prop.value()? {
+                ObjectFormatType::MEDIA_TYPE(media_type) => {
+                    if media_type.value()? != MediaType::AUDIO {
+                        return Err(crate::Error::TypeMismatch);
+                    }
+                }
+                ObjectFormatType::MEDIA_SUBTYPE(media_subtype) => {
+                    if media_subtype.value()? != MediaSubType::RAW {
+                        return Err(crate::Error::TypeMismatch);
+                    }
+                }
+                ObjectFormatType::AUDIO_FORMAT(value) => format = Some(value.value()?),
+                ObjectFormatType::AUDIO_RATE(value) => rate = Some(value.value()? as u32),
+                ObjectFormatType::AUDIO_CHANNELS(value) => channels = Some(value.value()? as u32),
+                ObjectFormatType::AUDIO_POSITION(value) => {
+                    position = value.value()?.map(AudioChannel::from).collect()
+                }
+                _ => {}
+            }
+        }
+        match (format, rate, channels) {
+            (Some(format), Some(rate), Some(channels)) => Ok(Self {
+                format,
+                rate,
+                channels,
+                position,
+            }),
+            _ => Err(crate::Error::ErrorMessage(
+                "Audio format, rate or channels are missing",
+            )),
+        }
+    }
+}
+
+/// Typed view of the input buffer, depends on the negotiated format.
+#[derive(Debug)]
+pub enum AudioBuffer<'a, S: AudioSample> {
+    Interleaved(InterleavedBuffer<'a, S>),
+    Planar(PlanarBuffer<'a, S>),
+}
+
+impl<'a, S: AudioSample> AudioBuffer<'a, S> {
+    pub fn from_buffer(buffer: &'a buffers::BufferRef, info: &AudioInfo) -> crate::Result<Self> {
+        if S::is_planar_format(info.format) {
+            Ok(Self::Planar(buffer.planar(info.format)?))
+        } else {
+            Ok(Self::Interleaved(
+                buffer.interleaved(info.format, info.channels)?,
+            ))
+        }
+    }
+
+    /// Number of frames
+    pub fn frames(&self) -> usize {
+        match self {
+            AudioBuffer::Interleaved(buffer) => buffer.frames(),
+            AudioBuffer::Planar(buffer) => buffer.frames(),
+        }
+    }
+}
+
+/// Typed view of the output buffer, depends on the negotiated format.
+#[derive(Debug)]
+pub enum AudioBufferMut<'a, S: AudioSample> {
+    Interleaved(InterleavedBufferMut<'a, S>),
+    Planar(PlanarBufferMut<'a, S>),
+}
+
+impl<'a, S: AudioSample> AudioBufferMut<'a, S> {
+    pub fn from_buffer(
+        buffer: &'a mut buffers::BufferRef,
+        info: &AudioInfo,
+    ) -> crate::Result<Self> {
+        if S::is_planar_format(info.format) {
+            Ok(Self::Planar(buffer.planar_mut(info.format)?))
+        } else {
+            Ok(Self::Interleaved(
+                buffer.interleaved_mut(info.format, info.channels)?,
+            ))
+        }
+    }
+
+    /// Max number of frames
+    pub fn frames(&self) -> usize {
+        match self {
+            AudioBufferMut::Interleaved(buffer) => buffer.frames(),
+            AudioBufferMut::Planar(buffer) => buffer.frames(),
+        }
+    }
+
+    /// Set the chunks size and stride for the written frames.
+    pub fn commit(self, frames: usize) -> crate::Result<()> {
+        match self {
+            AudioBufferMut::Interleaved(buffer) => buffer.commit(frames),
+            AudioBufferMut::Planar(buffer) => buffer.commit(frames),
+        }
+    }
+}
+
+pub type FormatCallback = Box<dyn FnMut(AudioInfo)>;
+/// Playback process callback, fills the buffer and returns the number of written frames.
+pub type PlaybackCallback<S> =
+    Box<dyn for<'a> FnMut(&AudioInfo, &mut AudioBufferMut<'a, S>) -> usize>;
+/// Capture process callback, receives the buffer with the captured frames.
+pub type CaptureCallback<S> = Box<dyn for<'a> FnMut(&AudioInfo, &AudioBuffer<'a, S>)>;
+
+enum ProcessCallback<S: AudioSample> {
+    Playback(PlaybackCallback<S>),
+    Capture(CaptureCallback<S>),
+}
+
+/// Builder of the [AudioStream].
+///
+/// The enum format param is built from the format, rate, channels and position choices,
+/// the buffers and meta params are updated when the format is negotiated.
+/// Process callback is called only when the negotiated format matches the sample type.
+pub struct AudioStreamBuilder<S: AudioSample> {
+    format: Option<ChoiceStructType<PodIdRef<AudioFormat>>>,
+    rate: Option<ChoiceStructType<PodIntRef>>,
+    channels: Option<ChoiceStructType<PodIntRef>>,
+    position: Vec<AudioChannel>,
+    latency: Option<(u32, u32)>,
+    properties: Properties,
+    target: StreamTarget,
+    flags: StreamFlags,
+    on_format: Option<FormatCallback>,
+    process: Option<ProcessCallback<S>>,
+}
+
+impl<S: AudioSample> Default for AudioStreamBuilder<S> {
+    fn default() -> Self {
+        Self {
+            format: None,
+            rate: None,
+            channels: None,
+            position: Vec::new(),
+            latency: None,
+            properties: Properties::default(),
+            target: StreamTarget::Any,
+            flags: StreamFlags::AUTOCONNECT | StreamFlags::MAP_BUFFERS,
+            on_format: None,
+            process: None,
+        }
+    }
+}
+
+impl<S: AudioSample> AudioStreamBuilder<S> {
+    /// Sample format choice, required.
+    /// Formats should match the sample type.
+    pub fn format(mut self, format: ChoiceStructType<PodIdRef<AudioFormat>>) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Sample rate choice, any rate if not set
+    pub fn rate(mut self, rate: ChoiceStructType<PodIntRef>) -> Self {
+        self.rate = Some(rate);
+        self
+    }
+
+    /// Channels count choice, any channels count if not set
+    pub fn channels(mut self, channels: ChoiceStructType<PodIntRef>) -> Self {
+        self.channels = Some(channels);
+        self
+    }
+
+    /// Channels positions
+    pub fn position(mut self, position: Vec<AudioChannel>) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Latency hint, the preferred quantum in frames for the given rate.
+    pub fn latency(mut self, frames: u32, rate: u32) -> Self {
+        self.latency = Some((frames, rate));
+        self
+    }
+
+    /// Additional stream properties
+    pub fn properties(mut self, properties: Properties) -> Self {
+        self.properties = properties;
+        self
+    }
+
+    /// Stream target, [StreamTarget::Any] by default
+    pub fn target(mut self, target: StreamTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Stream flags, [StreamFlags::AUTOCONNECT] and [StreamFlags::MAP_BUFFERS] by default.
+    /// [StreamFlags::RT_PROCESS] is not supported, the callbacks are called from the main loop.
+    /// [StreamFlags::MAP_BUFFERS] is required to access the buffers data.
+    pub fn flags(mut self, flags: StreamFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Callback is called when the format is negotiated
+    pub fn on_format(mut self, callback: FormatCallback) -> Self {
+        self.on_format = Some(callback);
+        self
+    }
+
+    /// Output stream process callback.
+    pub fn playback(mut self, callback: PlaybackCallback<S>) -> Self {
+        self.process = Some(ProcessCallback::Playback(callback));
+        self
+    }
+
+    /// Input stream process callback.
+    pub fn capture(mut self, callback: CaptureCallback<S>) -> Self {
+        self.process = Some(ProcessCallback::Capture(callback));
+        self
+    }
+
+    /// Create and connect the stream.
+    ///
+    /// Returns error if the format or the process callback are not set,
+    /// or if the [StreamFlags::RT_PROCESS] flag is set.
+    pub fn build(self, core: &CoreRef, name: &CStr) -> crate::Result<AudioStream<S>> {
+        if self.flags.contains(StreamFlags::RT_PROCESS) {
+            return Err(crate::Error::ErrorMessage(
+                "RT_PROCESS flag is not supported by the audio stream",
+            ));
+        }
+        let process = self.process.ok_or(crate::Error::ErrorMessage(
+            "Playback or capture callback is required",
+        ))?;
+        let format = self
+            .format
+            .ok_or(crate::Error::ErrorMessage("Audio format is required"))?;
+        let (direction, category) = match process {
+            ProcessCallback::Playback(_) => (Direction::OUTPUT, "Playback"),
+            ProcessCallback::Capture(_) => (Direction::INPUT, "Capture"),
+        };
+
+        let properties = self.properties;
+        set_default(&properties, pw_sys::PW_KEY_MEDIA_TYPE, "Audio");
+        set_default(&properties, pw_sys::PW_KEY_MEDIA_CATEGORY, category);
+        if let Some((frames, rate)) = self.latency {
+            set_default(
+                &properties,
+                pw_sys::PW_KEY_NODE_LATENCY,
+                &format!("{}/{}", frames, rate),
+            );
+        }
+        let stream = Rc::new(Stream::new(core, name, properties)?);
+        // Both callbacks are called from the main loop thread, the info is shared without locks
+        let info = Rc::new(RefCell::new(None));
+
+        let listener = StreamEventsBuilder::default()
+            .param_changed(Box::new({
+                let stream = Rc::downgrade(&stream);
+                let info = info.clone();
+                let mut on_format = self.on_format;
+                move |id, param| {
+                    if id == ParamType::FORMAT.into() {
+                        if let Some(stream) = stream.upgrade() {
+                            let negotiated = AudioInfo::from_format_param(param).ok();
+                            if let Some(negotiated) = &negotiated {
+                                if let Err(error) = update_stream_params::<S>(&stream, negotiated) {
+                                    set_error(&stream, error);
+                                }
+                                if let Some(callback) = &mut on_format {
+                                    callback(negotiated.clone());
+                                }
+                            }
+                            *info.borrow_mut() = negotiated;
+                        }
+                    }
+                }
+            }))
+            .process(Box::new({
+                let stream = Rc::downgrade(&stream);
+                let info = info.clone();
+                let mut process = process;
+                move || {
+                    if let Some(stream) = stream.upgrade() {
+                        if let Some(info) = info.borrow().as_ref() {
+                            on_process(&stream, info, &mut process);
+                        }
+                    }
+                }
+            }))
+            .build();
+        stream.add_listener(listener);
+
+        let positions: Vec<u32> = self.position.iter().map(|channel| channel.raw).collect();
+        let positions = AllocatedPodValueIterator::<PodIdRef<u32>>::new(positions);
+        let mut enum_format = ObjectEnumFormatBuilder::default()
+            .body_id(ParamType::ENUM_FORMAT.into())
+            .media_type(MediaType::AUDIO)
+            .media_subtype(MediaSubType::RAW)
+            .audio_format(format);
+        if let Some(rate) = self.rate {
+            enum_format = enum_format.audio_rate(rate);
+        }
+        if let Some(channels) = self.channels {
+            enum_format = enum_format.audio_channels(channels);
+        }
+        if !self.position.is_empty() {
+            enum_format = enum_format.audio_position(positions.iter());
+        }
+        let enum_format = enum_format.build()?;
+
+        stream.connect_to(
+            direction,
+            self.target,
+            self.flags,
+            &[enum_format.as_pod().upcast()],
+        )?;
+        Ok(AudioStream {
+            stream,
+            info,
+            phantom: PhantomData,
+        })
+    }
+}
+
+fn set_default(properties: &Properties, name: &[u8], value: &str) {
+    let key = key(name);
+    if properties.get(key).is_none() {
+        properties.set(key, &CString::new(value).unwrap());
+    }
+}
+
+fn update_stream_params<S: AudioSample>(stream: &Stream, info: &AudioInfo) -> crate::Result<()> {
+    let (blocks, stride) = if S::is_planar_format(info.format) {
+        (info.channels, size_of::<S>())
+    } else {
+        (1, info.channels as usize * size_of::<S>())
+    };
+    let buffers = ParamBuffersBuilder::default()
+        .body_id(ParamType::BUFFERS.into())
+        .buffers(ChoiceStructType::RANGE(PodRangeValue::new(
+            DEFAULT_BUFFERS,
+            MIN_BUFFERS,
+            MAX_BUFFERS,
+        )))
+        .blocks(ChoiceStructType::NONE(blocks as i32))
+        .stride(ChoiceStructType::NONE(stride as i32))
+        .build()?;
+    let meta_header = ParamMetaBuilder::default()
+        .body_id(ParamType::META.into())
+        .type_(MetaType::HEADER)
+        .size(ChoiceStructType::NONE(
+            size_of::<spa_sys::spa_meta_header>() as i32,
+        ))
+        .build()?;
+    let params: Vec<AllocPod<PodObjectRef>> = vec![buffers, meta_header];
+    let params: Vec<&PodRef> = params.iter().map(|param| param.as_pod().upcast()).collect();
+    stream.update_params(&params)
+}
+
+fn on_process<S: AudioSample>(stream: &Stream, info: &AudioInfo, process: &mut ProcessCallback<S>) {
+    if let Some(buffer) = stream.dequeue_buffer() {
+        match process {
+            ProcessCallback::Playback(callback) => {
+                if let Ok(mut view) = AudioBufferMut::from_buffer(buffer.buffer_mut(), info) {
+                    let frames = callback(info, &mut view).min(view.frames());
+                    if let Err(error) = view.commit(frames) {
+                        set_error(stream, error);
+                    }
+                }
+            }
+            ProcessCallback::Capture(callback) => {
+                if let Ok(view) = AudioBuffer::from_buffer(buffer.buffer(), info) {
+                    callback(info, &view);
+                }
+            }
+        }
+        if let Err(error) = stream.queue_buffer(buffer) {
+            set_error(stream, error);
+        }
+    }
+}
+
+/// Move the stream to the error state
+fn set_error(stream: &Stream, error: crate::Error) {
+    let res = match error {
+        crate::Error::ErrorCode(code) => -(code as i32),
+        _ => -EINVAL,
+    };
+    let message = CString::new(error.to_string()).unwrap_or_default();
+    // Nothing else can be done if the error cannot be reported
+    stream.set_error(res, &message).ok();
+}
+
+/// Audio stream created by [AudioStreamBuilder].
+/// The stream is disconnected and destroyed on drop.
+#[derive(Debug)]
+pub struct AudioStream<S: AudioSample> {
+    stream: Rc<Stream>,
+    info: Rc<RefCell<Option<AudioInfo>>>,
+    phantom: PhantomData<S>,
+}
+
+impl<S: AudioSample> AudioStream<S> {
+    pub fn stream(&self) -> &Stream {
+        &self.stream
+    }
+
+    /// Negotiated format, None until the format is negotiated
+    pub fn info(&self) -> Option<AudioInfo> {
+        self.info.borrow().clone()
+    }
+}
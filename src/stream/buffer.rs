@@ -3,6 +3,7 @@
  */
 use pipewire_wrapper_proc_macro::RawWrapper;
 
+use crate::core_api::require_library_version;
 use crate::spa::buffers;
 use crate::wrapper::RawWrapper;
 
@@ -30,7 +31,10 @@ impl BufferRef {
         self.raw.size
     }
 
-    // pub fn requested(&self) -> u64 {
-    //     self.raw.requested
-    // }
+    /// Suggested amount of data to provide for the playback streams, available since PipeWire 0.3.49.
+    #[cfg(feature = "v0_3_56")]
+    pub fn requested(&self) -> crate::Result<u64> {
+        require_library_version(0, 3, 49)?;
+        Ok(self.raw.requested)
+    }
 }
@@ -39,6 +39,7 @@ pub type AddBufferCallback = Box<dyn for<'a> FnMut(&'a BufferRef)>;
 pub type RemoveBufferCallback = Box<dyn for<'a> FnMut(&'a BufferRef)>;
 pub type ProcessCallback = Box<dyn FnMut()>;
 pub type DrainedCallback = Box<dyn FnMut()>;
+/// Command callback, [CommandRef::node_command] can be used to get the typed command value.
 pub type CommandCallback = Box<dyn for<'a> FnMut(&'a CommandRef)>;
 pub type TriggerDoneCallback = Box<dyn FnMut()>;
 
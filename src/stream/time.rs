@@ -51,12 +51,7 @@ impl Debug for TimeRef {
 
 impl Default for TimeRef {
     fn default() -> Self {
-        TimeRef::from_raw(pw_sys::pw_time {
-            now: 0,
-            rate: spa_sys::spa_fraction { num: 0, denom: 0 },
-            ticks: 0,
-            delay: 0,
-            queued: 0,
-        })
+        // The struct fields depend on the PipeWire version
+        TimeRef::from_raw(unsafe { std::mem::zeroed() })
     }
 }
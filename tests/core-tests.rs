@@ -3,6 +3,9 @@
  */
 
 mod tests {
+    use pipewire_wrapper::core_api::core::CoreRef;
+    use pipewire_wrapper::core_api::loop_::Loop;
+    use pipewire_wrapper::core_api::proxy::Proxied;
     use pipewire_wrapper::core_api::{core::Core, registry::events::RegistryEventsBuilder};
     use pipewire_wrapper::listeners::OwnListeners;
     use std::time::Duration;
@@ -31,4 +34,31 @@ mod tests {
 
         main_loop.run().unwrap();
     }
+
+    #[test]
+    fn test_registry_globals_future() {
+        let core = Core::default();
+        let main_loop = core.context().main_loop().clone();
+        let registry = core.get_registry(0).unwrap();
+        let globals = registry.globals_future().unwrap();
+
+        let task = main_loop
+            .spawn_local({
+                let main_loop = main_loop.clone();
+                async move {
+                    let globals = globals.await;
+                    main_loop.quit().unwrap();
+                    globals
+                }
+            })
+            .unwrap();
+        let _timer = main_loop.quit_after(Duration::from_secs(3)).unwrap();
+
+        main_loop.run().unwrap();
+
+        let globals = task.take_output().unwrap();
+        assert!(globals
+            .iter()
+            .any(|g| g.type_info() == CoreRef::type_info()));
+    }
 }
@@ -3,17 +3,24 @@
  */
 
 mod tests {
+    use pipewire_wrapper::core_api::loop_::channel::LoopChannel;
     use pipewire_wrapper::core_api::loop_::Loop;
+    use std::io::Write;
+    use std::os::fd::OwnedFd;
+    use std::os::unix::net::UnixStream;
     use std::rc::Rc;
     use std::{
         sync::{
             atomic::{AtomicBool, Ordering},
-            Mutex,
+            Arc, Mutex,
         },
         time::Duration,
     };
 
     use pipewire_wrapper::core_api::main_loop::MainLoop;
+    use pipewire_wrapper::spa::loop_::{IOSource, TimerSource};
+
+    const SPA_IO_IN: u32 = 1 << 0;
 
     #[test]
     fn test_init_main_loop() {
@@ -98,4 +105,120 @@ mod tests {
 
         main_loop.run().unwrap();
     }
+
+    #[test]
+    fn test_spawn_local() {
+        let main_loop = MainLoop::default();
+
+        let first = main_loop.spawn_local(async { 1 }).unwrap();
+        let second = main_loop
+            .spawn_local({
+                let main_loop = main_loop.clone();
+                async move {
+                    let result = first.await + 1;
+                    main_loop.quit().unwrap();
+                    result
+                }
+            })
+            .unwrap();
+        let _timer = main_loop.quit_after(Duration::from_secs(3)).unwrap();
+
+        main_loop.run().unwrap();
+
+        assert_eq!(second.take_output(), Some(2));
+    }
+
+    #[test]
+    fn test_loop_channel_from_thread() {
+        let main_loop = MainLoop::default();
+        let received = Rc::new(Mutex::new(Vec::new()));
+
+        let (sender, receiver) = LoopChannel::bounded_channel::<u32>(2);
+        let _attached_receiver = receiver
+            .attach_each(&main_loop, {
+                let main_loop = main_loop.clone();
+                let received = received.clone();
+                move |value| {
+                    let mut received = received.lock().unwrap();
+                    received.push(value);
+                    if received.len() == 10 {
+                        main_loop.quit().unwrap();
+                    }
+                }
+            })
+            .unwrap();
+        let thread = std::thread::spawn(move || {
+            for value in 0..10 {
+                sender.send(value).unwrap();
+            }
+        });
+        let _timer = main_loop.quit_after(Duration::from_secs(3)).unwrap();
+
+        main_loop.run().unwrap();
+        thread.join().unwrap();
+
+        assert_eq!(*received.lock().unwrap(), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_loop_hooks() {
+        let main_loop = MainLoop::default();
+        let before_count = Arc::new(Mutex::new(0));
+        let after_count = Arc::new(Mutex::new(0));
+
+        let _hooks = main_loop
+            .get_loop()
+            .control()
+            .add_hook(
+                Some(Box::new({
+                    let before_count = before_count.clone();
+                    move || *before_count.lock().unwrap() += 1
+                })),
+                Some(Box::new({
+                    let after_count = after_count.clone();
+                    move || *after_count.lock().unwrap() += 1
+                })),
+            )
+            .unwrap();
+        let _timer = main_loop.quit_after(Duration::from_millis(100)).unwrap();
+
+        main_loop.run().unwrap();
+
+        assert!(*before_count.lock().unwrap() > 0);
+        assert!(*after_count.lock().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_io_source() {
+        struct Sources {
+            io: IOSource<MainLoop>,
+            timer: TimerSource<MainLoop>,
+        }
+
+        let main_loop = MainLoop::default();
+        let (mut writer, reader) = UnixStream::pair().unwrap();
+        let received = Rc::new(AtomicBool::new(false));
+
+        let sources = Sources {
+            io: main_loop
+                .add_io(OwnedFd::from(reader), SPA_IO_IN, {
+                    let main_loop = main_loop.clone();
+                    let received = received.clone();
+                    move |_fd, _mask| {
+                        received.store(true, Ordering::Relaxed);
+                        main_loop.quit().unwrap();
+                    }
+                })
+                .unwrap(),
+            timer: main_loop.quit_after(Duration::from_secs(3)).unwrap(),
+        };
+        writer.write_all(&[1]).unwrap();
+
+        main_loop.run().unwrap();
+        drop(sources);
+
+        assert!(received.load(Ordering::Relaxed));
+        // The reader is closed with the source
+        assert!(writer.write_all(&[1]).is_err());
+    }
 }
@@ -30,4 +30,11 @@ mod tests {
         assert!(!pw.debug_is_category_enabled(&CString::new("wrong_debug_category").unwrap()));
         println!("In valgrind {:?}", pw.in_valgrind());
     }
+
+    #[test]
+    fn test_library_version() {
+        println!("Library version {:?}", PipeWire::get_library_version());
+        assert!(PipeWire::check_library_version(0, 3, 0));
+        assert!(!PipeWire::check_library_version(999, 0, 0));
+    }
 }
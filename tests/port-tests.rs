@@ -47,7 +47,7 @@ mod tests {
 
         let _timer = main_loop.quit_after(Duration::from_secs(1)).unwrap();
 
-        port_receiver
+        let _attached_port_receiver = port_receiver
             .attach(
                 &main_loop,
                 Box::new(move |new_ports| {
@@ -122,7 +122,7 @@ mod tests {
 
         let _timer = main_loop.quit_after(Duration::from_secs(1)).unwrap();
 
-        port_receiver
+        let _attached_port_receiver = port_receiver
             .attach(
                 &main_loop,
                 Box::new(move |new_ports| {
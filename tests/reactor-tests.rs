@@ -0,0 +1,39 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+#[cfg(feature = "reactor")]
+mod tests {
+    use std::os::fd::AsRawFd;
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use pipewire_wrapper::core_api::loop_::reactor::LoopReactor;
+    use pipewire_wrapper::core_api::loop_::Loop;
+    use pipewire_wrapper::core_api::main_loop::MainLoop;
+
+    #[test]
+    fn test_reactor_dispatch() {
+        let main_loop = MainLoop::default();
+        let reactor = LoopReactor::new(main_loop.clone()).unwrap();
+        assert!(reactor.as_raw_fd() >= 0);
+
+        let event_signal = Rc::new(AtomicBool::new(false));
+        let event = main_loop
+            .add_event({
+                let event_signal = event_signal.clone();
+                move |_count| event_signal.store(true, Ordering::Relaxed)
+            })
+            .unwrap();
+        event.signal().unwrap();
+
+        for _ in 0..10 {
+            reactor.dispatch().unwrap();
+            if event_signal.load(Ordering::Relaxed) {
+                break;
+            }
+        }
+
+        assert!(event_signal.load(Ordering::Relaxed))
+    }
+}
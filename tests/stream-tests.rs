@@ -0,0 +1,169 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+mod tests {
+    use std::ffi::CString;
+
+    use pipewire_wrapper::core_api::core::Core;
+    use pipewire_wrapper::core_api::properties::Properties;
+    use pipewire_wrapper::spa::param::ParamType;
+    use pipewire_wrapper::spa::pod::choice::enum_::PodEnumValue;
+    use pipewire_wrapper::spa::pod::choice::ChoiceStructType;
+    use pipewire_wrapper::spa::pod::id::PodIdRef;
+    use pipewire_wrapper::spa::pod::iterator::AllocatedPodValueIterator;
+    use pipewire_wrapper::spa::pod::object::format::{
+        AudioFormat, MediaSubType, MediaType, ObjectFormatBuilder,
+    };
+    use pipewire_wrapper::spa::pod::object::param_port_config::Direction;
+    use pipewire_wrapper::spa::pod::object::prop::AudioChannel;
+    use pipewire_wrapper::spa::pod::Upcast;
+    use pipewire_wrapper::stream::audio::{AudioInfo, AudioStreamBuilder};
+    use pipewire_wrapper::stream::{Stream, StreamFlags, StreamTarget};
+
+    #[test]
+    fn test_resolve_stream_target() {
+        let core = Core::default();
+
+        let target = StreamTarget::NodeId(42).resolve(&core).unwrap();
+        assert_eq!(target, StreamTarget::NodeId(42));
+
+        let target = StreamTarget::NodeName(CString::new("missing.node.name").unwrap());
+        assert!(target.resolve(&core).is_err());
+    }
+
+    #[test]
+    fn test_connect_to_object_serial() {
+        let core = Core::default();
+        let stream = Stream::new(
+            &core,
+            CString::new("Test stream").unwrap().as_ref(),
+            Properties::default(),
+        )
+        .unwrap();
+
+        stream
+            .connect_to(
+                Direction::OUTPUT,
+                StreamTarget::ObjectSerial(42),
+                StreamFlags::INACTIVE,
+                &[],
+            )
+            .unwrap();
+
+        let target_object = CString::new("target.object").unwrap();
+        assert_eq!(
+            stream.get_properties().get(&target_object),
+            Some(CString::new("42").unwrap().as_ref())
+        );
+        stream.disconnect().unwrap();
+    }
+
+    #[test]
+    fn test_connect_keeps_target_object() {
+        let core = Core::default();
+        let target_object = CString::new("target.object").unwrap();
+        let target = CString::new("test.target").unwrap();
+        let properties = Properties::default();
+        properties.set(&target_object, &target);
+        let stream = Stream::new(
+            &core,
+            CString::new("Test stream").unwrap().as_ref(),
+            properties,
+        )
+        .unwrap();
+
+        stream
+            .connect(Direction::OUTPUT, StreamFlags::INACTIVE, &[])
+            .unwrap();
+        assert_eq!(
+            stream.get_properties().get(&target_object),
+            Some(target.as_ref())
+        );
+        stream.disconnect().unwrap();
+
+        stream
+            .connect_to(
+                Direction::OUTPUT,
+                StreamTarget::Any,
+                StreamFlags::INACTIVE,
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            stream.get_properties().get(&target_object),
+            Some(target.as_ref())
+        );
+        stream.disconnect().unwrap();
+    }
+
+    #[test]
+    fn test_audio_info_from_format_param() {
+        let position = AllocatedPodValueIterator::<PodIdRef>::new(vec![
+            AudioChannel::FL.into(),
+            AudioChannel::FR.into(),
+        ]);
+        let format = ObjectFormatBuilder::default()
+            .body_id(ParamType::FORMAT.into())
+            .media_type(MediaType::AUDIO)
+            .media_subtype(MediaSubType::RAW)
+            .audio_format(AudioFormat::F32P)
+            .audio_rate(48000)
+            .audio_channels(2)
+            .audio_position(position.iter())
+            .build()
+            .unwrap();
+
+        let info = AudioInfo::from_format_param(format.as_pod().upcast()).unwrap();
+        assert_eq!(
+            info,
+            AudioInfo {
+                format: AudioFormat::F32P,
+                rate: 48000,
+                channels: 2,
+                position: vec![AudioChannel::FL, AudioChannel::FR],
+            }
+        );
+
+        let format = ObjectFormatBuilder::default()
+            .body_id(ParamType::FORMAT.into())
+            .media_type(MediaType::VIDEO)
+            .media_subtype(MediaSubType::RAW)
+            .build()
+            .unwrap();
+        assert!(AudioInfo::from_format_param(format.as_pod().upcast()).is_err());
+    }
+
+    #[test]
+    fn test_audio_stream_builder() {
+        let core = Core::default();
+
+        let builder = AudioStreamBuilder::<f32>::default()
+            .playback(Box::new(|_info, buffer| buffer.frames()));
+        assert!(builder
+            .build(&core, CString::new("Test audio stream").unwrap().as_ref())
+            .is_err());
+
+        let stream = AudioStreamBuilder::<f32>::default()
+            .format(ChoiceStructType::ENUM(PodEnumValue::new(
+                AudioFormat::F32,
+                vec![AudioFormat::F32, AudioFormat::F32P],
+            )))
+            .channels(ChoiceStructType::NONE(2))
+            .rate(ChoiceStructType::NONE(48000))
+            .position(vec![AudioChannel::FL, AudioChannel::FR])
+            .latency(256, 48000)
+            .flags(StreamFlags::INACTIVE | StreamFlags::MAP_BUFFERS)
+            .playback(Box::new(|_info, buffer| buffer.frames()))
+            .build(&core, CString::new("Test audio stream").unwrap().as_ref())
+            .unwrap();
+
+        let node_latency = CString::new("node.latency").unwrap();
+        assert_eq!(
+            stream.stream().get_properties().get(&node_latency),
+            Some(CString::new("256/48000").unwrap().as_ref())
+        );
+        assert_eq!(stream.info(), None);
+        stream.stream().disconnect().unwrap();
+    }
+}
@@ -0,0 +1,91 @@
+/*
+ * SPDX-License-Identifier: MIT
+ */
+
+mod tests {
+    use std::time::Duration;
+
+    use pipewire_wrapper::core_api::context::Context;
+    use pipewire_wrapper::core_api::core::Core;
+    use pipewire_wrapper::core_api::loop_::Loop;
+    use pipewire_wrapper::core_api::properties::Properties;
+    use pipewire_wrapper::core_api::thread_loop::ThreadLoop;
+
+    #[test]
+    fn test_thread_loop_signal() {
+        let thread_loop = ThreadLoop::default();
+        thread_loop.start().unwrap();
+
+        let guard = thread_loop.lock_guard();
+        let timer = thread_loop
+            .add_timer({
+                let thread_loop = thread_loop.clone();
+                move |_expirations| {
+                    assert!(thread_loop.in_thread());
+                    thread_loop.signal(false);
+                }
+            })
+            .unwrap();
+        timer
+            .update(Duration::from_millis(100), Duration::ZERO, false)
+            .unwrap();
+
+        assert!(guard.timed_wait(5).unwrap());
+        drop(timer);
+        drop(guard);
+
+        thread_loop.stop();
+    }
+
+    #[test]
+    fn test_thread_loop_invoke() {
+        let thread_loop = ThreadLoop::default();
+        thread_loop.start().unwrap();
+
+        let result = thread_loop
+            .get_loop()
+            .loop_()
+            .invoke(true, {
+                let thread_loop = thread_loop.clone();
+                move |async_| {
+                    assert!(async_);
+                    assert!(thread_loop.in_thread());
+                    42
+                }
+            })
+            .unwrap();
+        assert_eq!(result, 42);
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        thread_loop
+            .get_loop()
+            .loop_()
+            .invoke(false, move |_async| {
+                sender.send(1).unwrap();
+                0
+            })
+            .unwrap();
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)), Ok(1));
+
+        thread_loop.stop();
+    }
+
+    #[test]
+    fn test_thread_loop_core() {
+        let thread_loop = ThreadLoop::default();
+        thread_loop.start().unwrap();
+
+        let guard = thread_loop.lock_guard();
+        let context =
+            Context::new_with_thread_loop(thread_loop.clone(), Properties::default()).unwrap();
+        assert!(context.thread_loop().is_some());
+        let core = Core::connect(context, Properties::default()).unwrap();
+        let registry = core.get_registry(0).unwrap();
+        assert!(core.roundtrip().unwrap().is_empty());
+        drop(registry);
+        drop(core);
+        drop(guard);
+
+        thread_loop.stop();
+    }
+}